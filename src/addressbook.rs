@@ -0,0 +1,97 @@
+//! `~/.bntui/addresses.toml`: labels for addresses you send to, one
+//! `label = "address"` per line. The send dialog picks from it, and `S`
+//! after a send adds the address that was just used. Encrypted like the
+//! other files in `~/.bntui` when `[storage] encrypt` is on.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Default)]
+pub struct AddressBook {
+    // (label, address), sorted by label
+    pub entries: Vec<(String, String)>,
+    // the file exists but couldn't be read; saving would overwrite it
+    load_error: Option<String>,
+}
+
+impl AddressBook {
+    pub fn path() -> Option<PathBuf> {
+        crate::app::bntui_dir().map(|d| d.join("addresses.toml"))
+    }
+
+    /// Read the address book, decrypting it with `key` if it is encrypted. A
+    /// missing file is an empty book; one that can't be read or doesn't
+    /// parse (e.g. a label given twice) is reported on stderr and left alone.
+    pub fn load(key: Option<&crate::crypt::Key>) -> AddressBook {
+        let Some(path) = Self::path() else {
+            return AddressBook::default();
+        };
+        let parsed = crate::crypt::read(&path, key).and_then(|text| {
+            toml::from_str::<BTreeMap<String, String>>(&text.unwrap_or_default()).map_err(|e| e.to_string())
+        });
+        match parsed {
+            Ok(map) => AddressBook {
+                entries: map.into_iter().collect(),
+                load_error: None,
+            },
+            Err(e) => {
+                eprintln!("warning: ignoring {}: {}", path.display(), e);
+                AddressBook {
+                    entries: vec![],
+                    load_error: Some(format!("{} can't be read, fix it first", path.display())),
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn label_for(&self, address: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(_, a)| a == address)
+            .map(|(label, _)| label.as_str())
+    }
+
+    /// Save `address` under `label` and write the file, encrypted if `key` is
+    /// given. A label already used for another address is refused rather
+    /// than overwritten.
+    pub fn add(&mut self, label: &str, address: &str, key: Option<&crate::crypt::Key>) -> Result<(), String> {
+        if let Some(ref e) = self.load_error {
+            return Err(e.clone());
+        }
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("Label is required".to_string());
+        }
+        if let Some(existing) = self.label_for(address) {
+            return Err(format!("Already saved as \"{}\"", existing));
+        }
+        let pos = match self.entries.binary_search_by(|(l, _)| l.as_str().cmp(label)) {
+            Ok(_) => return Err(format!("\"{}\" is already used for another address", label)),
+            Err(pos) => pos,
+        };
+        self.entries.insert(pos, (label.to_string(), address.to_string()));
+        if let Err(e) = self.save(key) {
+            self.entries.remove(pos);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn save(&self, key: Option<&crate::crypt::Key>) -> Result<(), String> {
+        let path = Self::path().ok_or("No home directory")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let map: BTreeMap<&str, &str> = self
+            .entries
+            .iter()
+            .map(|(l, a)| (l.as_str(), a.as_str()))
+            .collect();
+        let text = toml::to_string(&map).map_err(|e| e.to_string())?;
+        crate::crypt::write(&path, &text, key).map_err(|e| format!("can't write {}: {}", path.display(), e))
+    }
+}
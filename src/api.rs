@@ -1,14 +1,74 @@
 use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use std::sync::{Arc, Mutex};
+
+/// Tries at connecting for a send, and the wait before the first retry
+/// (doubled for each one after). Retries stop once a connect timeout has
+/// passed since the first try, so the UI isn't held up for long.
+const SEND_ATTEMPTS: u32 = 3;
+const SEND_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Whether requests go through an HTTP proxy.
+#[derive(Clone)]
+pub enum ProxyMode {
+    /// honor HTTP_PROXY/HTTPS_PROXY/NO_PROXY from the environment
+    Env,
+    /// always connect directly
+    Off,
+    /// send everything through this proxy
+    Url(String),
+}
+
+/// How the client reaches the daemon: proxy, and how long connections are
+/// kept warm between polls.
+#[derive(Clone)]
+pub struct ApiConfig {
+    pub proxy: ProxyMode,
+    /// bearer token to send instead of reading the cookie file
+    pub token: Option<String>,
+    /// idle pooled connections are closed after this long
+    pub pool_idle_timeout: std::time::Duration,
+    /// idle connections kept open per host
+    pub pool_max_idle_per_host: usize,
+    /// a request (connect to last byte) is abandoned after this long, so a
+    /// hung daemon can't freeze the UI
+    pub timeout: std::time::Duration,
+    /// connecting alone is abandoned after this long
+    pub connect_timeout: std::time::Duration,
+    /// accept any TLS certificate (a self-signed one on a remote node)
+    pub accept_invalid_certs: bool,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            proxy: ProxyMode::Env,
+            token: None,
+            // well past the 3 s data poll, so every poll finds a warm connection
+            pool_idle_timeout: std::time::Duration::from_secs(90),
+            // the polls are sequential; a couple of spares covers the odd overlap
+            pool_max_idle_per_host: 4,
+            timeout: std::time::Duration::from_secs(5),
+            connect_timeout: std::time::Duration::from_secs(3),
+            accept_invalid_certs: false,
+        }
+    }
+}
 
 pub struct ApiClient {
     client: reqwest::Client,
     base_url: String,
+    // kept for `reconnect`
+    cookie_path: String,
+    config: ApiConfig,
 }
 
 impl ApiClient {
-    pub fn new(base_url: &str, cookie_path: &str) -> Result<Self, String> {
-        let token = std::fs::read_to_string(cookie_path)
-            .map_err(|e| format!("can't read cookie: {}", e))?;
+    pub fn new(base_url: &str, cookie_path: &str, config: &ApiConfig) -> Result<Self, String> {
+        let token = match config.token {
+            Some(ref token) => token.clone(),
+            None => std::fs::read_to_string(cookie_path)
+                .map_err(|e| format!("can't read cookie: {}", e))?,
+        };
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -17,60 +77,91 @@ impl ApiClient {
                 .map_err(|e| format!("bad token: {}", e))?,
         );
 
-        let client = reqwest::Client::builder()
+        let builder = reqwest::Client::builder()
             .default_headers(headers)
+            .pool_idle_timeout(config.pool_idle_timeout)
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .danger_accept_invalid_certs(config.accept_invalid_certs)
+            // keeps NAT and firewall state alive for a remote daemon
+            .tcp_keepalive(std::time::Duration::from_secs(30));
+        let client = with_proxy(builder, &config.proxy)?
             .build()
             .map_err(|e| format!("client build failed: {}", e))?;
 
         Ok(Self {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
+            cookie_path: cookie_path.to_string(),
+            config: config.clone(),
         })
     }
 
-    pub async fn get_status(&self) -> Result<crate::types::DaemonStats, reqwest::Error> {
-        self.client
-            .get(format!("{}/api/status", self.base_url))
-            .send()
-            .await?
-            .json()
-            .await
+    /// A fresh client for the same daemon, with new connections and the
+    /// cookie read again (a restarted daemon writes a new one).
+    pub fn reconnect(&self) -> Result<Self, String> {
+        Self::new(&self.base_url, &self.cookie_path, &self.config)
     }
 
-    pub async fn get_mempool(&self) -> Result<crate::types::MempoolStats, reqwest::Error> {
-        self.client
-            .get(format!("{}/api/mempool", self.base_url))
-            .send()
-            .await?
-            .json()
-            .await
+    /// A client with no daemon behind it (snapshot replay); every request
+    /// fails immediately.
+    pub fn offline() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: "http://127.0.0.1:0".to_string(),
+            cookie_path: String::new(),
+            config: ApiConfig::default(),
+        }
     }
 
-    pub async fn get_balance(&self) -> Result<crate::types::BalanceResponse, reqwest::Error> {
-        self.client
-            .get(format!("{}/api/wallet/balance", self.base_url))
-            .send()
-            .await?
-            .json()
-            .await
+    pub async fn get_status(&self) -> Result<crate::types::DaemonStats, String> {
+        self.get_json("/api/status").await
     }
 
-    pub async fn get_address(&self) -> Result<crate::types::AddressResponse, reqwest::Error> {
-        self.client
-            .get(format!("{}/api/wallet/address", self.base_url))
+    pub async fn get_mempool(&self) -> Result<crate::types::MempoolStats, String> {
+        self.get_json("/api/mempool").await
+    }
+
+    pub async fn get_balance(&self) -> Result<crate::types::BalanceResponse, String> {
+        self.get_json("/api/wallet/balance").await
+    }
+
+    pub async fn get_address(&self) -> Result<crate::types::AddressResponse, String> {
+        self.get_json("/api/wallet/address").await
+    }
+
+    pub async fn create_wallet(&self) -> Result<(), String> {
+        let resp = self
+            .client
+            .post(format!("{}/api/wallet/create", self.base_url))
             .send()
-            .await?
-            .json()
             .await
+            .map_err(|e| e.to_string())?;
+
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let body = resp.text().await.unwrap_or_default();
+            Err(format!("HTTP {}: {}", status.as_u16(), body.trim()))
+        }
     }
 
-    pub async fn get_mining(&self) -> Result<crate::types::MiningStatus, reqwest::Error> {
-        self.client
-            .get(format!("{}/api/mining", self.base_url))
+    /// Ask the wallet for a fresh receive address.
+    pub async fn new_address(&self) -> Result<crate::types::AddressResponse, String> {
+        let resp = self
+            .client
+            .post(format!("{}/api/wallet/address/new", self.base_url))
             .send()
-            .await?
-            .json()
             .await
+            .map_err(|e| e.to_string())?;
+
+        read_json(resp, "/api/wallet/address/new").await
+    }
+
+    pub async fn get_mining(&self) -> Result<crate::types::MiningStatus, String> {
+        self.get_json("/api/mining").await
     }
 
     pub async fn start_mining(&self) -> Result<(), reqwest::Error> {
@@ -98,56 +189,434 @@ impl ApiClient {
         Ok(())
     }
 
-    pub async fn send_to(&self, address: &str, amount: u64) -> Result<String, String> {
-        let resp = self
-            .client
-            .post(format!("{}/api/wallet/send", self.base_url))
-            .json(&serde_json::json!({
-                "address": address,
-                "amount": amount
-            }))
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
+    /// Send `amount` to `address`. With `fee` unset the daemon picks the fee.
+    pub async fn send_to_with_fee(
+        &self,
+        address: &str,
+        amount: u64,
+        fee: Option<u64>,
+    ) -> Result<String, String> {
+        let mut body = serde_json::json!({
+            "address": address,
+            "amount": amount
+        });
+        if let Some(fee) = fee {
+            body["fee"] = fee.into();
+        }
+        let started = std::time::Instant::now();
+        let mut attempt = 1;
+        let resp = loop {
+            match self
+                .client
+                .post(format!("{}/api/wallet/send", self.base_url))
+                .json(&body)
+                .send()
+                .await
+            {
+                Ok(resp) => break resp,
+                // the connection never opened, so the daemon got nothing and
+                // trying again can't send twice
+                Err(e) if e.is_connect() => {
+                    crate::log::write(&format!("send: attempt {} couldn't connect: {:?}", attempt, e));
+                    let delay = SEND_RETRY_DELAY * 2u32.pow(attempt - 1);
+                    if attempt == SEND_ATTEMPTS
+                        || started.elapsed() + delay >= self.config.connect_timeout
+                    {
+                        return Err(if attempt == 1 {
+                            "Daemon unreachable; nothing was sent".to_string()
+                        } else {
+                            format!("Daemon unreachable ({} tries); nothing was sent", attempt)
+                        });
+                    }
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                // the request may have arrived; a retry could pay twice
+                Err(e) => {
+                    crate::log::write(&format!("send: no reply: {:?}", e));
+                    return Err("No reply; the send may have gone through".to_string());
+                }
+            }
+        };
 
         let status = resp.status();
         let body = resp.text().await.unwrap_or_default();
         if status.is_success() {
-            // Try to extract txid from JSON response
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
-                if let Some(txid) = json
-                    .get("txid")
-                    .or(json.get("hash"))
-                    .and_then(|v| v.as_str())
-                {
-                    return Ok(txid.to_string());
+            match extract_txid(&body) {
+                Some(txid) => Ok(txid),
+                None => {
+                    crate::log::write(&format!("send: no txid in the {}-byte response", body.len()));
+                    Ok("sent".to_string())
                 }
             }
-            // Maybe bare txid string
-            let trimmed = body.trim().trim_matches('"');
-            if !trimmed.is_empty() && trimmed.len() <= 64 {
-                Ok(trimmed.to_string())
-            } else {
-                Ok("sent".to_string())
-            }
         } else {
             Err(if body.is_empty() {
-                format!("Send failed (HTTP {})", status)
+                format!("Rejected (HTTP {})", status)
             } else {
-                body
+                format!("Rejected: {}", body)
             })
         }
     }
 
-    pub async fn get_block(
-        &self,
-        height: u64,
-    ) -> Result<crate::types::BlockResponse, reqwest::Error> {
-        self.client
-            .get(format!("{}/api/block/{}", self.base_url, height))
+    /// Unlock an encrypted wallet for `timeout_secs`.
+    pub async fn unlock_wallet(&self, passphrase: &str, timeout_secs: u64) -> Result<(), String> {
+        let resp = self
+            .client
+            .post(format!("{}/api/wallet/unlock", self.base_url))
+            .json(&serde_json::json!({ "passphrase": passphrase, "timeout": timeout_secs }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let body = resp.text().await.unwrap_or_default();
+            Err(error_message(status.as_u16(), &body))
+        }
+    }
+
+    pub async fn lock_wallet(&self) -> Result<(), String> {
+        let resp = self
+            .client
+            .post(format!("{}/api/wallet/lock", self.base_url))
             .send()
-            .await?
-            .json()
             .await
+            .map_err(|e| e.to_string())?;
+
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let body = resp.text().await.unwrap_or_default();
+            Err(error_message(status.as_u16(), &body))
+        }
+    }
+
+    /// Broadcast an externally built, signed transaction.
+    pub async fn send_raw(&self, hex: &str) -> Result<String, String> {
+        let resp = self
+            .client
+            .post(format!("{}/api/tx/broadcast", self.base_url))
+            .json(&serde_json::json!({ "hex": hex }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        if status.is_success() {
+            Ok(extract_txid(&body).unwrap_or_else(|| "broadcast".to_string()))
+        } else {
+            Err(error_message(status.as_u16(), &body))
+        }
+    }
+
+    /// Height of the block containing `txid`, or `None` if the node knows
+    /// the transaction but it isn't mined yet.
+    pub async fn get_tx_height(&self, txid: &str) -> Result<Option<u64>, String> {
+        let json: serde_json::Value = self.get_json(&format!("/api/tx/{}", txid)).await?;
+        Ok(["block_height", "height"]
+            .iter()
+            .find_map(|k| json.get(*k).and_then(|v| v.as_u64())))
+    }
+
+    /// Inputs and outputs of a transaction. Lists the daemon reports only as
+    /// counts come back empty.
+    pub async fn get_tx_detail(&self, txid: &str) -> Result<crate::types::TxDetail, String> {
+        let json: serde_json::Value = self.get_json(&format!("/api/tx/{}", txid)).await?;
+        let list = |key: &str| -> Result<Vec<crate::types::TxIo>, String> {
+            match json.get(key) {
+                Some(v) if v.is_array() => serde_json::from_value(v.clone())
+                    .map_err(|e| format!("bad response from /api/tx: {}", e)),
+                _ => Ok(Vec::new()),
+            }
+        };
+        Ok(crate::types::TxDetail {
+            inputs: list("inputs")?,
+            outputs: list("outputs")?,
+        })
+    }
+
+    /// Daemon version string. Accepts a bare JSON string or an object with a
+    /// `version` field.
+    pub async fn get_version(&self) -> Result<String, String> {
+        let json: serde_json::Value = self.get_json("/api/version").await?;
+        json.as_str()
+            .or_else(|| json.get("version").and_then(|v| v.as_str()))
+            .map(str::to_string)
+            .ok_or_else(|| "bad response from /api/version: no version".to_string())
+    }
+
+    /// Connected peers. Accepts a bare array or an object with a `peers` list.
+    pub async fn get_peers(&self) -> Result<Vec<crate::types::PeerInfo>, String> {
+        let json: serde_json::Value = self.get_json("/api/peers").await?;
+        let list = match json.get("peers") {
+            Some(peers) => peers.clone(),
+            None => json,
+        };
+        serde_json::from_value(list).map_err(|e| format!("bad response from /api/peers: {}", e))
+    }
+
+    /// Pending transactions. Accepts a bare array or one under `txs` /
+    /// `transactions`.
+    pub async fn get_mempool_txs(&self) -> Result<Vec<crate::types::MempoolTx>, String> {
+        let json: serde_json::Value = self.get_json("/api/mempool/txs").await?;
+        let list = match json.get("txs").or_else(|| json.get("transactions")) {
+            Some(txs) => txs.clone(),
+            None => json,
+        };
+        serde_json::from_value(list).map_err(|e| format!("bad response from /api/mempool/txs: {}", e))
+    }
+
+    pub async fn get_fee_estimate(&self) -> Result<crate::types::FeeEstimate, String> {
+        self.get_json("/api/fee/estimate").await
+    }
+
+    pub async fn get_block(&self, height: u64) -> Result<crate::types::BlockResponse, String> {
+        self.get_json(&format!("/api/block/{}", height)).await
+    }
+
+    /// GET `path` and decode the JSON body (see `read_json`).
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        let resp = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        read_json(resp, path).await
+    }
+}
+
+/// Decode a JSON response from `path`. The status is checked first, so a
+/// rejected cookie or a daemon error page becomes "HTTP <code>: <reason>"
+/// rather than a decode error; a 2xx HTML page (a web server or proxy on
+/// the configured port) gets an explicit hint.
+async fn read_json<T: serde::de::DeserializeOwned>(resp: reqwest::Response, path: &str) -> Result<T, String> {
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    let html = content_type.contains("html") || body.trim_start().starts_with('<');
+
+    if !status.is_success() {
+        let reason = if html {
+            html_title(&body)
+        } else if body.trim().is_empty() {
+            None
+        } else {
+            Some(error_message(status.as_u16(), &body))
+        };
+        let reason = reason
+            .or_else(|| status.canonical_reason().map(str::to_string))
+            .unwrap_or_else(|| "error".to_string());
+        return Err(format!("HTTP {}: {}", status.as_u16(), first_line(&reason, 120)));
+    }
+    if html {
+        let got = if content_type.is_empty() { "HTML" } else { content_type.as_str() };
+        return Err(format!(
+            "expected JSON from {} but got {} — is this the API port?",
+            path, got
+        ));
+    }
+    serde_json::from_str(&body).map_err(|e| format!("bad response from {}: {}", path, e))
+}
+
+/// Text of the `<title>` of an HTML page, if it has a non-empty one.
+fn html_title(body: &str) -> Option<String> {
+    let lower = body.to_ascii_lowercase();
+    let start = lower.find("<title")?;
+    let start = start + lower[start..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = body[start..end].split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+/// First line of `text`, cut to `max` characters, so a stack trace or a
+/// whole page doesn't end up in a one-line flash.
+fn first_line(text: &str, max: usize) -> String {
+    let line = text.lines().next().unwrap_or("").trim();
+    if line.chars().count() > max {
+        format!("{}…", line.chars().take(max - 1).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Route `builder` through `proxy`.
+fn with_proxy(
+    builder: reqwest::ClientBuilder,
+    proxy: &ProxyMode,
+) -> Result<reqwest::ClientBuilder, String> {
+    Ok(match proxy {
+        ProxyMode::Env => builder,
+        ProxyMode::Off => builder.no_proxy(),
+        ProxyMode::Url(url) => builder.proxy(
+            reqwest::Proxy::all(url).map_err(|e| format!("bad proxy {}: {}", url, e))?,
+        ),
+    })
+}
+
+/// Live BNT price from a user-supplied URL (`--price-url`), fetched by a
+/// background task so a slow price server never holds up the data poll.
+/// Separate from `ApiClient` so the daemon cookie never goes to a third party.
+pub struct PriceFeed {
+    latest: Arc<Mutex<Option<f64>>>,
+}
+
+impl PriceFeed {
+    /// Fetch the rate from `url` now and then every `interval`.
+    pub fn start(url: &str, proxy: &ProxyMode, interval: std::time::Duration) -> Result<Self, String> {
+        let builder = reqwest::Client::builder().timeout(std::time::Duration::from_secs(5));
+        let client = with_proxy(builder, proxy)?
+            .build()
+            .map_err(|e| format!("client build failed: {}", e))?;
+        let latest = Arc::new(Mutex::new(None));
+        let shared = Arc::clone(&latest);
+        let url = url.to_string();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                match fetch_price(&client, &url).await {
+                    Ok(rate) => {
+                        if let Ok(mut latest) = shared.lock() {
+                            *latest = Some(rate);
+                        }
+                    }
+                    // keep the last good rate
+                    Err(e) => crate::log::write(&format!("price: {}", e)),
+                }
+            }
+        });
+        Ok(Self { latest })
+    }
+
+    /// The last rate fetched, or None before the first one comes in.
+    pub fn latest(&self) -> Option<f64> {
+        self.latest.lock().ok().and_then(|latest| *latest)
+    }
+}
+
+/// Fetch the rate. Accepts a bare number (or numeric string) or JSON with
+/// the number under `price`/`usd`/`rate`/`last` at any depth, e.g.
+/// `{"blocknet":{"usd":0.12}}`.
+async fn fetch_price(client: &reqwest::Client, url: &str) -> Result<f64, String> {
+    let resp = client.get(url).send().await.map_err(|e| e.to_string())?;
+    let status = resp.status();
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(body.trim()).map_err(|e| format!("bad price response: {}", e))?;
+    find_price(&json)
+        .filter(|p| p.is_finite() && *p >= 0.0)
+        .ok_or_else(|| "no price in response".to_string())
+}
+
+fn find_price(json: &serde_json::Value) -> Option<f64> {
+    let as_num = |v: &serde_json::Value| v.as_f64().or_else(|| v.as_str()?.trim().parse().ok());
+    if let Some(p) = as_num(json) {
+        return Some(p);
+    }
+    let obj = json.as_object()?;
+    for key in ["price", "usd", "rate", "last"] {
+        if let Some(p) = obj.get(key).and_then(as_num) {
+            return Some(p);
+        }
+    }
+    obj.values().find_map(find_price)
+}
+
+/// Whether a `get_block` error means the node doesn't have the block (e.g.
+/// it was pruned) rather than a connection problem.
+pub fn is_not_found(err: &str) -> bool {
+    err.starts_with("HTTP 404")
+}
+
+/// Whether an error means the daemon rejected our token (a stale or wrong
+/// cookie) rather than being unreachable.
+pub fn is_auth_error(err: &str) -> bool {
+    err.starts_with("HTTP 401") || err.starts_with("HTTP 403")
+}
+
+/// Whether a `get_address` error means the node has no wallet loaded yet
+/// (as opposed to a connection problem).
+pub fn is_no_wallet_error(err: &str) -> bool {
+    let lower = err.to_ascii_lowercase();
+    lower.starts_with("http 404")
+        || lower.contains("no wallet")
+        || lower.contains("wallet not")
+}
+
+/// Whether a send error means the wallet is encrypted and locked.
+pub fn is_locked_error(err: &str) -> bool {
+    // the daemon answers "wallet is locked, use 'unlock' first"
+    let lower = err.to_ascii_lowercase();
+    lower.contains("is locked") || lower.contains("wallet locked")
+}
+
+/// Whether `s` looks like a serialized transaction: non-empty, whole bytes,
+/// hex digits only.
+pub fn is_plausible_tx_hex(s: &str) -> bool {
+    !s.is_empty() && s.len().is_multiple_of(2) && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Readable reason from a failed response: the `error`/`message` field of a
+/// JSON body if there is one, else the body itself.
+fn error_message(status: u16, body: &str) -> String {
+    let body = body.trim();
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(body) {
+        for key in ["error", "message"] {
+            if let Some(msg) = json.get(key).and_then(|v| v.as_str()) {
+                return msg.to_string();
+            }
+        }
+    }
+    if body.is_empty() {
+        format!("HTTP {}", status)
+    } else {
+        body.to_string()
+    }
+}
+
+/// Pull a txid out of a send response. Accepts a JSON object with the txid
+/// under a handful of known keys, or a bare (optionally quoted) string.
+fn extract_txid(body: &str) -> Option<String> {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(body) {
+        let candidates = [
+            json.get("txid"),
+            json.get("hash"),
+            json.get("tx"),
+            json.get("transaction"),
+            json.get("result").and_then(|r| r.get("txid")),
+            json.get("result"),
+            Some(&json),
+        ];
+        for value in candidates.into_iter().flatten() {
+            if let Some(txid) = value.as_str().and_then(clean_txid) {
+                return Some(txid);
+            }
+        }
+    }
+    clean_txid(body)
+}
+
+fn clean_txid(raw: &str) -> Option<String> {
+    let trimmed = raw.trim().trim_matches('"').trim();
+    if trimmed.len() == 64 && trimmed.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(trimmed.to_string())
+    } else {
+        None
     }
 }
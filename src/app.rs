@@ -1,3 +1,5 @@
+use crate::config::Config;
+use crate::theme::Theme;
 use crate::cube;
 use crate::types;
 
@@ -6,9 +8,428 @@ pub enum InputMode {
     SendDialog {
         address: String,
         amount: String,
+        // blank lets the daemon pick the fee
+        fee: String,
+        // 0 address, 1 amount, 2 fee, 3 address book
         focused: u8,
         error: Option<String>,
+        // what has been typed to confirm an amount over `[wallet] max_send`
+        confirm: Option<String>,
+        // address book entry last picked into `address`
+        saved: Option<usize>,
     },
+    // label for the address of the last send, for the address book
+    SaveAddress {
+        address: String,
+        label: String,
+        error: Option<String>,
+    },
+    /// full-screen view of one transaction of a loaded block
+    TxDetail {
+        block_height: u64,
+        tx_index: usize,
+        // inputs/outputs from `/api/tx/{txid}`, or why they're missing
+        detail: Result<types::TxDetail, String>,
+        scroll: u16,
+    },
+    CreateWallet {
+        error: Option<String>,
+    },
+    // y/n before locking the wallet, which sends then need the passphrase for
+    LockWallet {
+        error: Option<String>,
+    },
+    FindTx {
+        query: String,
+        error: Option<String>,
+    },
+    // passphrase for an encrypted wallet, shown masked; `send` is retried
+    // once the wallet is unlocked
+    UnlockWallet {
+        passphrase: String,
+        error: Option<String>,
+        send: Option<PendingSend>,
+    },
+    // edits `App::mempool_txs_filter` live; Esc puts `previous` back
+    MempoolFilter {
+        previous: String,
+    },
+    GotoBlock {
+        input: String,
+        error: Option<String>,
+    },
+    Broadcast {
+        hex: String,
+        // validated and waiting for a second Enter
+        confirm: bool,
+        error: Option<String>,
+    },
+    // the receive address and the ones rotated away from; row 0 is the
+    // current one
+    Receive {
+        selected: usize,
+    },
+}
+
+/// Length of one UI tick; animation and polling count in ticks.
+pub const TICK_MS: u64 = 33;
+
+/// Ticks each loop covers while animations are paused: the loop wakes a
+/// fifth as often and polls keep their pace.
+pub const PAUSED_TICK_STEP: u64 = 5;
+
+// default poll intervals, in ticks (--status-interval / --data-interval)
+pub const STATUS_POLL_TICKS: u64 = 30;
+pub const DATA_POLL_TICKS: u64 = 90;
+
+/// Failed status polls in a row before the API client is rebuilt; retries
+/// then back off from `RECONNECT_BASE_TICKS`, doubling up to
+/// `RECONNECT_MAX_TICKS`.
+pub const RECONNECT_AFTER: u32 = 3;
+pub const RECONNECT_BASE_TICKS: u64 = 60;
+pub const RECONNECT_MAX_TICKS: u64 = 900;
+
+/// Blocknet's target block time; `--target-block-time` overrides it.
+pub const DEFAULT_TARGET_BLOCK_TIME: u64 = 300;
+
+/// Status polls kept for the sync rate and ETA. A target moving by more
+/// than `SYNC_TARGET_JUMP` blocks in one poll starts the measurement over.
+pub const SYNC_SAMPLES: usize = 60;
+pub const SYNC_TARGET_JUMP: u64 = 10;
+
+/// First daemon releases serving the optional endpoints. A daemon reporting
+/// an older version isn't asked; one that doesn't say is probed and the
+/// endpoint dropped on its first 404.
+pub const PEERS_SINCE: (u32, u32, u32) = (0, 2, 0);
+pub const MEMPOOL_TXS_SINCE: (u32, u32, u32) = (0, 3, 0);
+pub const FEE_ESTIMATE_SINCE: (u32, u32, u32) = (0, 3, 0);
+
+/// Rotated-away receive addresses kept for the receive dialog.
+pub const PREVIOUS_ADDRESSES: usize = 5;
+
+/// Blocks loaded at startup, unless sized from the terminal or `--blocks`.
+pub const DEFAULT_BACKFILL: u64 = 1000;
+
+/// Points kept per sparkline history; `[display] history_len` or
+/// `--history-len` overrides it within `MIN_HISTORY_LEN..=MAX_HISTORY_LEN`.
+pub const DEFAULT_HISTORY_LEN: usize = 200;
+pub const MIN_HISTORY_LEN: usize = 10;
+pub const MAX_HISTORY_LEN: usize = 10_000;
+
+/// Blocks kept in memory before the oldest are dropped; `[display]
+/// max_blocks` or `--max-blocks` overrides it.
+pub const DEFAULT_MAX_BLOCKS: u64 = 5000;
+pub const MIN_MAX_BLOCKS: u64 = 100;
+
+/// Where a backfill has got to: the next height to fetch, the lowest one
+/// wanted, and 404s in a row so far (a pruned node).
+pub struct Backfill {
+    pub next: u64,
+    pub floor: u64,
+    pub misses: u32,
+}
+
+/// A send held back by a locked wallet, to retry after unlocking.
+pub struct PendingSend {
+    pub address: String,
+    pub amount: u64,
+    pub fee: Option<u64>,
+}
+
+/// Seconds the wallet stays unlocked after the passphrase prompt.
+pub const UNLOCK_SECS: u64 = 300;
+
+/// One send, as recorded in `~/.bntui/tx.log` and kept for the session.
+#[derive(Clone)]
+pub struct TxHistoryEntry {
+    pub timestamp: u64,
+    pub txid: String,
+    pub address: String,
+    pub amount: u64,
+}
+
+impl TxHistoryEntry {
+    /// Parse a `<unix time> <txid> <address> <atomic amount>` line.
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let entry = TxHistoryEntry {
+            timestamp: fields.next()?.parse().ok()?,
+            txid: fields.next()?.to_string(),
+            address: fields.next()?.to_string(),
+            amount: fields.next()?.parse().ok()?,
+        };
+        fields.next().is_none().then_some(entry)
+    }
+}
+
+/// Older blocks loaded below a go-to target, so it isn't the last one.
+pub const GOTO_CONTEXT: u64 = 16;
+
+/// Entries kept in the in-app event log.
+pub const EVENT_LOG_CAP: usize = 200;
+
+pub struct Event {
+    pub time: u64,
+    pub msg: String,
+}
+
+/// The values the "what changed" flash compares, taken before a manual
+/// refresh, a reconnect or a daemon restart.
+pub struct Checkpoint {
+    height: u64,
+    balance: Option<u64>,
+    mempool: Option<u32>,
+    mining: Option<bool>,
+}
+
+/// Directory for bntui's own files (`~/.bntui`).
+pub fn bntui_dir() -> Option<std::path::PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| std::path::PathBuf::from(home).join(".bntui"))
+}
+
+/// Write `text` to `~/.bntui/<prefix>-<unix time>.<ext>` and return the path.
+fn write_export(prefix: &str, ext: &str, text: &str) -> Result<std::path::PathBuf, String> {
+    let dir = bntui_dir().ok_or_else(|| "HOME is not set".to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    write_timestamped(&dir, prefix, ext, text)
+}
+
+/// Write `text` to `<dir>/<prefix>-<unix time>.<ext>` and return the path.
+fn write_timestamped(
+    dir: &std::path::Path,
+    prefix: &str,
+    ext: &str,
+    text: &str,
+) -> Result<std::path::PathBuf, String> {
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = dir.join(format!("{}-{}.{}", prefix, ts, ext));
+    std::fs::write(&path, text).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Write a plain-text screen dump to `~/.bntui/screen-<ts>.txt`.
+pub fn write_screen(text: &str) -> Result<std::path::PathBuf, String> {
+    write_export("screen", "txt", text)
+}
+
+/// How much detail the grid's block info panel shows.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InfoDensity {
+    Minimal,
+    Normal,
+    Verbose,
+}
+
+impl InfoDensity {
+    pub fn next(self) -> Self {
+        match self {
+            InfoDensity::Minimal => InfoDensity::Normal,
+            InfoDensity::Normal => InfoDensity::Verbose,
+            InfoDensity::Verbose => InfoDensity::Minimal,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InfoDensity::Minimal => "minimal",
+            InfoDensity::Normal => "normal",
+            InfoDensity::Verbose => "verbose",
+        }
+    }
+}
+
+/// How the grid's left panel splits its height between the cube and the
+/// block info/tx list.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LeftPanelMode {
+    Balanced,
+    // cube only
+    Cube,
+    // block info and tx list only
+    Info,
+}
+
+impl LeftPanelMode {
+    pub fn next(self) -> Self {
+        match self {
+            LeftPanelMode::Balanced => LeftPanelMode::Cube,
+            LeftPanelMode::Cube => LeftPanelMode::Info,
+            LeftPanelMode::Info => LeftPanelMode::Balanced,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LeftPanelMode::Balanced => "balanced",
+            LeftPanelMode::Cube => "cube",
+            LeftPanelMode::Info => "info",
+        }
+    }
+}
+
+/// Series drawn by the Chain panel's chart.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChartMetric {
+    Difficulty,
+    // seconds since the previous block
+    BlockTime,
+    TxCount,
+}
+
+impl ChartMetric {
+    pub fn next(self) -> Self {
+        match self {
+            ChartMetric::Difficulty => ChartMetric::BlockTime,
+            ChartMetric::BlockTime => ChartMetric::TxCount,
+            ChartMetric::TxCount => ChartMetric::Difficulty,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartMetric::Difficulty => "diff",
+            ChartMetric::BlockTime => "block time",
+            ChartMetric::TxCount => "txs/block",
+        }
+    }
+
+    /// One value per loaded block, oldest first (block time starts at the
+    /// second block).
+    pub fn series(self, blocks: &[types::BlockResponse]) -> Vec<u64> {
+        match self {
+            ChartMetric::Difficulty => blocks.iter().map(|b| b.difficulty).collect(),
+            ChartMetric::BlockTime => blocks
+                .windows(2)
+                .map(|w| w[1].timestamp.saturating_sub(w[0].timestamp))
+                .collect(),
+            ChartMetric::TxCount => blocks.iter().map(|b| b.tx_count as u64).collect(),
+        }
+    }
+
+    /// A value formatted for the lo/avg/hi line.
+    pub fn format(self, v: u64) -> String {
+        match self {
+            ChartMetric::BlockTime => format!("{}s", v),
+            _ => v.to_string(),
+        }
+    }
+}
+
+/// File format for the grid's block export (`E`).
+#[derive(Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// How non-selected grid cells are drawn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GridCellStyle {
+    // solid cell, tx count by color only
+    Flat,
+    // cell height also encodes tx count (▁ … █)
+    Bars,
+}
+
+/// Formula driving the mining panel's plasma field.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PlasmaStyle {
+    Waves,
+    Rings,
+    Noise,
+}
+
+impl PlasmaStyle {
+    pub fn next(self) -> Self {
+        match self {
+            PlasmaStyle::Waves => PlasmaStyle::Rings,
+            PlasmaStyle::Rings => PlasmaStyle::Noise,
+            PlasmaStyle::Noise => PlasmaStyle::Waves,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlasmaStyle::Waves => "waves",
+            PlasmaStyle::Rings => "rings",
+            PlasmaStyle::Noise => "noise",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        [PlasmaStyle::Waves, PlasmaStyle::Rings, PlasmaStyle::Noise]
+            .into_iter()
+            .find(|s| s.label() == label)
+    }
+}
+
+/// UI state persisted to `~/.bntui/state.json` between runs.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SavedState {
+    pub view: Option<u8>,
+    pub plasma: Option<String>,
+    pub mine_threads: Option<u32>,
+    // heights attributed to our miner
+    pub mined: Vec<u64>,
+    // grid selection, by height since indices shift as the chain grows;
+    // unset while following the tip
+    pub selected_height: Option<u64>,
+}
+
+/// The endpoints polled on a timer, for remembering why each last failed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PollSource {
+    Status,
+    Mempool,
+    Balance,
+    Mining,
+}
+
+impl PollSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            PollSource::Status => "status",
+            PollSource::Mempool => "mempool",
+            PollSource::Balance => "balance",
+            PollSource::Mining => "mining",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Congestion {
+    Normal,
+    Elevated,
+    Congested,
+}
+
+/// Everything needed to redraw the UI without a daemon, written by the
+/// snapshot key and read back by `--replay`. Leaves out the wallet address.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Snapshot {
+    pub current_view: u8,
+    pub tick_count: u64,
+    pub selected: usize,
+    pub grid_scroll_offset: usize,
+    pub follow_tip: bool,
+    pub split_view: bool,
+    pub status: Option<types::DaemonStats>,
+    pub mempool: Option<types::MempoolStats>,
+    pub balance: Option<types::BalanceResponse>,
+    pub mining: Option<types::MiningStatus>,
+    pub chain_blocks: Vec<types::BlockResponse>,
+    #[serde(default)]
+    pub prune_height: Option<u64>,
+    pub mempool_history: Vec<u64>,
+    pub mempool_size_history: Vec<u64>,
+    pub mempool_fee_history: Vec<u64>,
 }
 
 pub struct FlashMessage {
@@ -19,67 +440,388 @@ pub struct FlashMessage {
 }
 
 pub struct App {
+    pub config: Config,
+    /// colors from `config.theme`
+    pub theme: Theme,
     pub current_view: u8,
     pub tick_count: u64,
     pub block_cubes: Vec<cube::SpinCube>,
     pub chain_blocks: Vec<types::BlockResponse>,
+    // lowest height a pruned node still serves; nothing below is fetched
+    pub prune_height: Option<u64>,
+    // older blocks still being loaded in the background
+    pub backfill: Option<Backfill>,
+    // how many blocks load_initial fetches
+    pub backfill_depth: u64,
+    // most blocks kept loaded; trim_blocks drops the oldest beyond this
+    pub max_blocks: u64,
     pub selected: usize,
     pub grid_scroll_offset: usize,
     pub blocks_per_row: usize,
+    // keep selection on the newest block as new blocks arrive
+    pub follow_tip: bool,
+    // side-by-side dashboard + grid toggle, and whether the last frame was
+    // wide enough to actually render it
+    pub split_view: bool,
+    pub split_active: bool,
+    // terminal width and height on the last draw
+    pub screen_size: (u16, u16),
+    pub info_density: InfoDensity,
+    // grid cells show block age glyphs and the gutter clock times
+    pub grid_timestamps: bool,
+    pub grid_cell_style: GridCellStyle,
+    pub left_panel_mode: LeftPanelMode,
+    pub chart_metric: ChartMetric,
+    // --export-format, for the grid's `E` export
+    pub export_format: ExportFormat,
+    // tx picked by the find-tx prompt or the ↑/↓ cursor, highlighted in
+    // the tx list
+    pub highlight_txid: Option<String>,
+    // PageUp/PageDown offset into the tx list of the block at
+    // tx_scroll_height; any other selected block lists from the top
+    pub tx_scroll: usize,
+    pub tx_scroll_height: u64,
+    // rows the tx list had on the last draw, one page of scrolling
+    pub tx_list_rows: usize,
     pub status: Option<types::DaemonStats>,
     pub mempool: Option<types::MempoolStats>,
     pub balance: Option<types::BalanceResponse>,
     pub wallet_address: Option<String>,
+    // addresses auto-rotation moved away from, newest first
+    pub previous_addresses: Vec<String>,
+    // node answered but has no wallet to hand out an address from
+    pub wallet_missing: bool,
     pub mining: Option<types::MiningStatus>,
+    // probed once at startup; None if the daemon doesn't say
+    pub daemon_version: Option<String>,
+    // cleared for daemons older than PEERS_SINCE or once /api/peers 404s
+    pub peers_supported: bool,
+    // fetched on demand while the peer summary is open
+    pub peers: Option<Vec<types::PeerInfo>>,
+    pub peers_error: Option<String>,
+    pub show_peer_summary: bool,
+    // first row shown in the peers view
+    pub peers_scroll: usize,
+    // peer rows the view had on the last draw, for clamping the scroll
+    pub peers_rows: usize,
+    // cleared by the daemon version or once /api/mempool/txs 404s; the panel
+    // then keeps to the totals
+    pub mempool_txs_supported: bool,
+    // fetched on demand while the mempool list is open
+    pub mempool_txs: Option<Vec<types::MempoolTx>>,
+    pub mempool_txs_error: Option<String>,
+    // the Mempool panel lists transactions instead of the totals
+    pub show_mempool_txs: bool,
+    // lowest fee first instead of highest
+    pub mempool_txs_ascending: bool,
+    // txid prefix the list is narrowed to
+    pub mempool_txs_filter: String,
+    // cleared by the daemon version or once /api/fee/estimate 404s; the send
+    // dialog then has no hint
+    pub fee_estimate_supported: bool,
+    // fetched each time the send dialog opens
+    pub fee_estimate: Option<types::FeeEstimate>,
+    // price of 1 BNT from --price/--price-url or [fiat]; None hides fiat
+    pub fiat_rate: Option<f64>,
+    pub show_fiat: bool,
+    // poll intervals in ticks
+    pub status_poll_ticks: u64,
+    pub data_poll_ticks: u64,
+    // tick of the last successful fetch of each data source
+    pub last_status_tick: Option<u64>,
+    pub last_mempool_tick: Option<u64>,
+    pub last_balance_tick: Option<u64>,
+    pub last_mining_tick: Option<u64>,
+    // first dashboard panel shown when they are stacked, and how far it can
+    // go (0 unless the terminal is too narrow for the 2x2 layout)
+    pub dashboard_scroll: usize,
+    pub dashboard_max_scroll: usize,
+    // space freezes the plasma, cube and constellation; animation_ticks
+    // only counts ticks they ran for
+    pub animations_paused: bool,
+    pub animation_ticks: u64,
     // plasma visualizer state
+    pub plasma_style: PlasmaStyle,
     pub plasma_t: f32,
     pub plasma_intensity: f32,
     pub prev_blocks_found: u64,
+    // heights our miner found, as far as attribute_mined can tell
+    pub mined_by_us: std::collections::BTreeSet<u64>,
+    // grid selection from state.json, applied once the blocks are loaded
+    pub restore_height: Option<u64>,
+    // blocks_found as of the last attribution, and finds not yet matched
+    // to a loaded block
+    pub found_seen: Option<u64>,
+    pub unattributed_found: u64,
+    // hash_count and blocks_found when the current mining run was first
+    // seen, so luck covers this run rather than the daemon's lifetime
+    pub mining_run_start: Option<(u64, u64)>,
+    pub mined_scan_height: u64,
+    // grid shows only mined_by_us blocks at full color
+    pub show_only_mine: bool,
+    // (total balance, chain height) marked with `b` on the dashboard
+    pub balance_mark: Option<(u64, u64)>,
+    // new block held in place and blinking (height, until tick), with
+    // display.new_block_hold set
+    pub held_block: Option<(u64, u64)>,
     pub shockwave_t: f32,
     // next block timer
+    pub target_block_time_secs: u64,
     pub prev_chain_height: u64,
     pub block_found_display: f32,
-    // mempool history for sparklines
+    // (when, sync_progress) per status poll while syncing, oldest first,
+    // and the sync target they were taken against. Wall clock rather than
+    // ticks: a tick runs longer than TICK_MS while drawing
+    pub sync_samples: Vec<(std::time::Instant, u64)>,
+    pub sync_target: u64,
+    // mempool history for sparklines, capped at history_len points
     pub mempool_history: Vec<u64>,
     pub mempool_size_history: Vec<u64>,
     pub mempool_fee_history: Vec<u64>,
+    pub history_len: usize,
     pub threads_pending_restart: Option<u64>,
+    // last explicit --mine-threads value, persisted
+    pub mine_threads: Option<u32>,
     pub flash_message: Option<FlashMessage>,
+    // deep reorg warning, shown until dismissed
+    pub reorg_banner: Option<String>,
     pub input_mode: InputMode,
-    pub tx_history: Vec<String>,
+    // sends made this session, oldest first
+    pub tx_history: Vec<TxHistoryEntry>,
+    // destination of the last successful send, offered to the address book
+    pub last_send_address: Option<String>,
+    pub address_book: crate::addressbook::AddressBook,
+    // tx.log as read when the history view was opened (this session's sends
+    // when tx.log is off), newest first, or why it couldn't be read
+    pub tx_log_entries: Result<Vec<TxHistoryEntry>, String>,
+    pub tx_log_selected: usize,
+    // rendering a snapshot; no daemon to poll
+    pub replay: bool,
+    // --accessible: ASCII only, textual summaries instead of graphics
+    pub accessible: bool,
+    // --cube-style: how the grid's selected-block cube is drawn
+    pub cube_style: cube::CubeStyle,
+    // --readonly: every key that would change the node or wallet is refused
+    pub readonly: bool,
+    // key for ~/.bntui files when storage.encrypt is on
+    pub cipher: Option<crate::crypt::Key>,
+    // resolved Blocknet directory (unset in replay mode)
+    pub blocknet_dir: Option<std::path::PathBuf>,
+    // recent events, oldest first, capped at EVENT_LOG_CAP
+    pub events: std::collections::VecDeque<Event>,
+    pub show_events: bool,
+    // last status poll failed; used to log outages/recoveries once
+    pub poll_failing: bool,
+    // why the last poll of each `PollSource` failed; None once one succeeds
+    pub poll_errors: [Option<String>; 4],
+    // status polls failed in a row, and the tick of the next reconnect
+    // attempt once there are RECONNECT_AFTER of them
+    pub status_failures: u32,
+    pub reconnect_at: u64,
+    // taken before a refresh/reconnect, with the flash label; diffed once
+    // fresh data is in
+    pub checkpoint: Option<(&'static str, Checkpoint)>,
 }
 
 impl App {
     pub fn new() -> App {
         App {
+            config: Config::default(),
+            theme: Theme::default(),
             current_view: 1,
             tick_count: 0,
             block_cubes: vec![],
             chain_blocks: vec![],
+            prune_height: None,
+            backfill: None,
+            backfill_depth: DEFAULT_BACKFILL,
+            max_blocks: DEFAULT_MAX_BLOCKS,
             selected: 0,
             grid_scroll_offset: 0,
             blocks_per_row: 20,
+            follow_tip: true,
+            split_view: false,
+            split_active: false,
+            screen_size: (0, 0),
+            info_density: InfoDensity::Normal,
+            grid_timestamps: false,
+            grid_cell_style: GridCellStyle::Flat,
+            left_panel_mode: LeftPanelMode::Balanced,
+            chart_metric: ChartMetric::Difficulty,
+            export_format: ExportFormat::default(),
+            highlight_txid: None,
+            tx_scroll: 0,
+            tx_scroll_height: 0,
+            tx_list_rows: 0,
             status: None,
             mempool: None,
             balance: None,
             wallet_address: None,
+            previous_addresses: vec![],
+            wallet_missing: false,
             mining: None,
+            daemon_version: None,
+            peers_supported: true,
+            peers: None,
+            peers_error: None,
+            show_peer_summary: false,
+            peers_scroll: 0,
+            peers_rows: 0,
+            mempool_txs_supported: true,
+            mempool_txs: None,
+            mempool_txs_error: None,
+            show_mempool_txs: false,
+            mempool_txs_ascending: false,
+            fee_estimate_supported: true,
+            fee_estimate: None,
+            mempool_txs_filter: String::new(),
+            fiat_rate: None,
+            show_fiat: true,
+            status_poll_ticks: STATUS_POLL_TICKS,
+            data_poll_ticks: DATA_POLL_TICKS,
+            last_status_tick: None,
+            last_mempool_tick: None,
+            last_balance_tick: None,
+            last_mining_tick: None,
+            dashboard_scroll: 0,
+            dashboard_max_scroll: 0,
+            animations_paused: false,
+            animation_ticks: 0,
+            plasma_style: PlasmaStyle::Waves,
             plasma_t: 0.0,
             plasma_intensity: 0.0,
             prev_blocks_found: 0,
+            mined_by_us: Default::default(),
+            restore_height: None,
+            found_seen: None,
+            mining_run_start: None,
+            unattributed_found: 0,
+            mined_scan_height: 0,
+            show_only_mine: false,
+            balance_mark: None,
+            held_block: None,
             shockwave_t: -1.0,
+            target_block_time_secs: DEFAULT_TARGET_BLOCK_TIME,
             prev_chain_height: 0,
             block_found_display: 0.0,
+            sync_samples: vec![],
+            sync_target: 0,
             mempool_history: vec![],
             mempool_size_history: vec![],
             mempool_fee_history: vec![],
+            history_len: DEFAULT_HISTORY_LEN,
             threads_pending_restart: None,
+            mine_threads: None,
             flash_message: None,
+            reorg_banner: None,
             input_mode: InputMode::Normal,
             tx_history: vec![],
+            last_send_address: None,
+            address_book: Default::default(),
+            tx_log_entries: Ok(vec![]),
+            tx_log_selected: 0,
+            replay: false,
+            accessible: false,
+            cube_style: cube::CubeStyle::default(),
+            readonly: false,
+            cipher: None,
+            blocknet_dir: None,
+            events: std::collections::VecDeque::new(),
+            show_events: false,
+            poll_failing: false,
+            poll_errors: Default::default(),
+            status_failures: 0,
+            reconnect_at: 0,
+            checkpoint: None,
         }
     }
 
+    /// Whether the block grid is on screen (and receives navigation keys).
+    pub fn shows_grid(&self) -> bool {
+        self.current_view == 2 || self.split_active
+    }
+
+    pub fn shows_dashboard(&self) -> bool {
+        self.current_view == 1 || self.split_active
+    }
+
+    /// The daemon has stopped answering and the client is being rebuilt.
+    pub fn reconnecting(&self) -> bool {
+        self.status_failures >= RECONNECT_AFTER
+    }
+
+    /// Count a failed status poll, scheduling the next reconnect attempt
+    /// once they've piled up.
+    pub fn status_failed(&mut self) {
+        self.status_failures += 1;
+        if self.reconnecting() {
+            let shift = (self.status_failures - RECONNECT_AFTER).min(4);
+            self.reconnect_at =
+                self.tick_count + (RECONNECT_BASE_TICKS << shift).min(RECONNECT_MAX_TICKS);
+        }
+    }
+
+    /// Record how a poll of `source` went. The first failure is logged and
+    /// flashed, so an error page or a rejected cookie doesn't pass for data
+    /// that simply hasn't arrived yet.
+    pub fn poll_result(&mut self, source: PollSource, error: Option<&str>) {
+        let slot = &mut self.poll_errors[source as usize];
+        match error {
+            Some(e) if slot.is_none() => {
+                *slot = Some(e.to_string());
+                let msg = format!("{} poll failed: {}", source.label(), e);
+                self.event(msg.clone());
+                self.set_flash(msg);
+            }
+            Some(e) => *slot = Some(e.to_string()),
+            None => *slot = None,
+        }
+    }
+
+    pub fn poll_error(&self, source: PollSource) -> Option<&str> {
+        self.poll_errors[source as usize].as_deref()
+    }
+
+    /// Mempool transactions matching the txid filter, sorted by fee
+    /// (highest first unless `mempool_txs_ascending`).
+    pub fn filtered_mempool_txs(&self) -> Vec<&types::MempoolTx> {
+        let filter = self.mempool_txs_filter.to_ascii_lowercase();
+        let mut txs: Vec<_> = self
+            .mempool_txs
+            .iter()
+            .flatten()
+            .filter(|tx| tx.txid.to_ascii_lowercase().starts_with(&filter))
+            .collect();
+        txs.sort_by(|a, b| b.fee.cmp(&a.fee).then_with(|| a.txid.cmp(&b.txid)));
+        if self.mempool_txs_ascending {
+            txs.reverse();
+        }
+        txs
+    }
+
+    pub fn shows_peers(&self) -> bool {
+        self.current_view == 3
+    }
+
+    pub fn shows_history(&self) -> bool {
+        self.current_view == 4
+    }
+
+    /// Advance one frame of animation for whatever is on screen.
+    pub fn animate(&mut self) {
+        if !self.animations_paused {
+            self.animation_ticks += 1;
+            if self.shows_grid() && !self.block_cubes.is_empty() {
+                let speed = self.spin_speed();
+                self.update_selected_cube(speed);
+            }
+            if self.shows_dashboard() {
+                self.update_plasma();
+            }
+        }
+        self.update_block_found();
+    }
+
     pub fn update_selected_cube(&mut self, spin_speed: f32) {
         if !self.block_cubes.is_empty() {
             self.block_cubes[self.selected].update(0.033 * spin_speed);
@@ -87,8 +829,9 @@ impl App {
     }
 
     pub fn selected_block_time(&self) -> f32 {
+        let target = self.target_block_time_secs as f32;
         if self.selected == 0 {
-            return 300.0;
+            return target;
         }
         if let (Some(block), Some(prev)) = (
             self.chain_blocks.get(self.selected),
@@ -96,20 +839,144 @@ impl App {
         ) {
             block.timestamp.saturating_sub(prev.timestamp) as f32
         } else {
-            300.0
+            target
         }
     }
 
+    /// `secs` as a fraction of the target block time (1.0 = on target).
+    pub fn block_time_ratio(&self, secs: f32) -> f32 {
+        secs / self.target_block_time_secs.max(1) as f32
+    }
+
+    /// How full the "Next block" bar is `elapsed` seconds after the last
+    /// block: the target sits at the middle, and the bar is full at twice it.
+    pub fn next_block_progress(&self, elapsed: f32) -> f32 {
+        self.block_time_ratio(elapsed).min(2.0) / 2.0
+    }
+
     pub fn spin_speed(&self) -> f32 {
         let block_time = self.selected_block_time();
         if block_time <= 0.0 {
             return 3.0;
         }
-        (300.0 / block_time).clamp(0.3, 3.0)
+        (1.0 / self.block_time_ratio(block_time)).clamp(0.3, 3.0)
+    }
+
+    /// Drop the oldest loaded blocks beyond `max_blocks`, keeping the
+    /// selection on the same block (or the oldest one left) and the grid
+    /// scrolled to the same rows.
+    pub fn trim_blocks(&mut self) {
+        let excess = self.chain_blocks.len().saturating_sub(self.max_blocks as usize);
+        if excess == 0 {
+            return;
+        }
+        self.chain_blocks.drain(..excess);
+        self.block_cubes.drain(..excess.min(self.block_cubes.len()));
+        self.selected = self.selected.saturating_sub(excess);
+        // and the view on the same rows
+        self.grid_scroll_offset = self.grid_scroll_offset.saturating_sub(excess / self.blocks_per_row.max(1));
+        // the pruned band marks the node's limit, which is no longer
+        // right below the oldest loaded block
+        let oldest = self.chain_blocks.first().map_or(0, |b| b.height);
+        if self.prune_height.is_some_and(|h| h < oldest) {
+            self.prune_height = None;
+        }
+    }
+
+    /// Store a mining poll. The counters are noted when a run starts (or
+    /// the daemon restarted and reset them) and forgotten when it stops.
+    pub fn set_mining(&mut self, mining: types::MiningStatus) {
+        let reset = self
+            .mining_run_start
+            .is_some_and(|(hashes, found)| mining.hash_count < hashes || mining.blocks_found < found);
+        if !mining.running {
+            self.mining_run_start = None;
+        } else if self.mining_run_start.is_none() || reset {
+            self.mining_run_start = Some((mining.hash_count, mining.blocks_found));
+        }
+        self.mining = Some(mining);
+        self.last_mining_tick = Some(self.tick_count);
+    }
+
+    /// Luck of the current mining run against the newest block's
+    /// difficulty, once it has done some work.
+    pub fn mining_luck(&self) -> Option<f64> {
+        let mining = self.mining.as_ref()?;
+        let (hashes, found) = self.mining_run_start?;
+        let difficulty = self.chain_blocks.last().map_or(0, |b| b.difficulty);
+        types::mining_luck(
+            mining.blocks_found.saturating_sub(found),
+            mining.hash_count.saturating_sub(hashes),
+            difficulty,
+        )
+    }
+
+    /// Attribute blocks to our miner when its `blocks_found` count goes up:
+    /// the newest blocks loaded since the previous call are taken to be
+    /// ours, and finds whose block hasn't arrived yet wait for the next
+    /// ones. The daemon doesn't say which blocks it mined, so this is a best
+    /// guess covering blocks found while bntui was running.
+    pub fn attribute_mined(&mut self) {
+        let Some(found) = self.mining.as_ref().map(|m| m.blocks_found) else {
+            return;
+        };
+        // a restarted daemon counts from zero again
+        if let Some(seen) = self.found_seen
+            && found > seen
+        {
+            self.unattributed_found += found - seen;
+        }
+        self.found_seen = Some(found);
+        let mut attributed = vec![];
+        for block in self.chain_blocks.iter().rev() {
+            if self.unattributed_found == 0 || block.height <= self.mined_scan_height {
+                break;
+            }
+            if self.mined_by_us.insert(block.height) {
+                self.unattributed_found -= 1;
+                attributed.push(format!("{} {} {}", block.timestamp, block.height, block.hash));
+            }
+        }
+        self.mined_scan_height = self.chain_blocks.last().map_or(0, |b| b.height);
+        if let Some(dir) = bntui_dir()
+            && !attributed.is_empty()
+        {
+            let _ = std::fs::create_dir_all(&dir);
+            for line in attributed.iter().rev() {
+                if let Err(e) = crate::crypt::append_line(&dir.join("mined.log"), line, self.cipher.as_ref()) {
+                    crate::log::write(&format!("mined.log: {}", e));
+                }
+            }
+        }
+    }
+
+    /// Add the heights in `~/.bntui/mined.log` (`<block time> <height>
+    /// <hash>` lines, appended as blocks are attributed) to the ones from
+    /// state.json, which is only written on exit.
+    pub fn load_mined_log(&mut self) {
+        let Some(dir) = bntui_dir() else {
+            return;
+        };
+        match crate::crypt::read(&dir.join("mined.log"), self.cipher.as_ref()) {
+            Ok(text) => self.mined_by_us.extend(
+                text.unwrap_or_default()
+                    .lines()
+                    .filter_map(|line| line.split_whitespace().nth(1)?.parse::<u64>().ok()),
+            ),
+            Err(e) => crate::log::write(&format!("mined.log: {}", e)),
+        }
+    }
+
+    /// Loaded blocks attributed to our miner.
+    pub fn mined_in_view(&self) -> usize {
+        self.chain_blocks
+            .iter()
+            .filter(|b| self.mined_by_us.contains(&b.height))
+            .count()
     }
 
     pub fn update_plasma(&mut self) {
-        let is_mining = self.mining.as_ref().map_or(false, |m| m.running);
+        let is_mining = self.mining.as_ref().is_some_and(|m| m.running);
         let hashrate = self.mining.as_ref().map_or(0.0, |m| m.hashrate);
         let blocks_found = self.mining.as_ref().map_or(0, |m| m.blocks_found);
 
@@ -157,12 +1024,197 @@ impl App {
             &mut self.mempool_size_history,
             &mut self.mempool_fee_history,
         ] {
-            if h.len() > 200 {
-                h.drain(..h.len() - 200);
+            if h.len() > self.history_len {
+                h.drain(..h.len() - self.history_len);
             }
         }
     }
 
+    /// Switch to a fresh receive address, keeping the last
+    /// `PREVIOUS_ADDRESSES` ones for the receive dialog.
+    pub fn rotate_address(&mut self, address: String) {
+        if let Some(old) = self.wallet_address.replace(address) {
+            self.previous_addresses.insert(0, old);
+            self.previous_addresses.truncate(PREVIOUS_ADDRESSES);
+        }
+    }
+
+    /// Row `row` of the receive dialog: the current address, then the
+    /// previous ones.
+    pub fn receive_address(&self, row: usize) -> Option<&str> {
+        match row {
+            0 => self.wallet_address.as_deref(),
+            _ => self.previous_addresses.get(row - 1).map(String::as_str),
+        }
+    }
+
+    /// Record the daemon's version and turn off the optional endpoints it
+    /// predates. An unparseable version leaves them to the 404 fallback.
+    pub fn set_daemon_version(&mut self, version: Option<String>) {
+        if let Some(v) = version.as_deref().and_then(types::parse_version) {
+            self.peers_supported &= v >= PEERS_SINCE;
+            self.mempool_txs_supported &= v >= MEMPOOL_TXS_SINCE;
+            self.fee_estimate_supported &= v >= FEE_ESTIMATE_SINCE;
+        }
+        self.daemon_version = version;
+    }
+
+    /// Sample sync progress for `sync_rate`. Starts over when sync ends,
+    /// the target jumps, or progress goes backwards (a new sync peer).
+    pub fn record_sync(&mut self, stats: &types::DaemonStats) {
+        let restart = !stats.syncing
+            || stats.sync_target < self.sync_target
+            || stats.sync_target > self.sync_target + SYNC_TARGET_JUMP
+            || self.sync_samples.last().is_some_and(|&(_, p)| stats.sync_progress < p);
+        if restart {
+            self.sync_samples.clear();
+        }
+        self.sync_target = stats.sync_target;
+        if stats.syncing {
+            self.sync_samples.push((std::time::Instant::now(), stats.sync_progress));
+            if self.sync_samples.len() > SYNC_SAMPLES {
+                self.sync_samples.remove(0);
+            }
+        }
+    }
+
+    /// Blocks per second over the sampled window, once it spans some time.
+    pub fn sync_rate(&self) -> Option<f64> {
+        let (&(t0, p0), &(t1, p1)) = (self.sync_samples.first()?, self.sync_samples.last()?);
+        let secs = (t1 - t0).as_secs_f64();
+        (secs > 0.0).then(|| (p1 - p0) as f64 / secs)
+    }
+
+    /// Seconds until the sync target at the current rate; `None` while the
+    /// rate is unknown or stalled.
+    pub fn sync_eta(&self) -> Option<u64> {
+        let rate = self.sync_rate().filter(|&r| r > 0.0)?;
+        let &(_, progress) = self.sync_samples.last()?;
+        let left = self.sync_target.saturating_sub(progress);
+        Some((left as f64 / rate).round() as u64)
+    }
+
+    /// Blocks per minute between consecutive samples, oldest first.
+    pub fn sync_rate_series(&self) -> Vec<u64> {
+        self.sync_samples
+            .windows(2)
+            .map(|w| {
+                let secs = (w[1].0 - w[0].0).as_secs_f64().max(0.001);
+                ((w[1].1 - w[0].1) as f64 * 60.0 / secs) as u64
+            })
+            .collect()
+    }
+
+    /// Mempool load relative to the configured alert thresholds: elevated
+    /// from half the threshold, congested at or above it.
+    pub fn mempool_congestion(&self) -> Congestion {
+        let Some(ref mempool) = self.mempool else {
+            return Congestion::Normal;
+        };
+        let cfg = &self.config.mempool;
+        let count_ratio = mempool.count as f64 / cfg.alert_count.max(1) as f64;
+        let bytes_ratio = mempool.size_bytes as f64 / cfg.alert_bytes.max(1) as f64;
+        let ratio = count_ratio.max(bytes_ratio);
+        if ratio >= 1.0 {
+            Congestion::Congested
+        } else if ratio >= 0.5 {
+            Congestion::Elevated
+        } else {
+            Congestion::Normal
+        }
+    }
+
+    /// Format an amount for display in the panels, honoring the configured
+    /// precision. Send confirmations and copied values use `format_bnt`.
+    pub fn fmt_bnt(&self, atomic: u64) -> String {
+        match self.config.display.precision() {
+            Some(decimals) => types::format_bnt_fixed(atomic, decimals),
+            None => types::format_bnt(atomic),
+        }
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            height: self.status.as_ref().map_or(0, |s| s.chain_height),
+            balance: self.balance.as_ref().map(|b| b.total),
+            mempool: self.mempool.as_ref().map(|m| m.count),
+            mining: self.mining.as_ref().map(|m| m.running),
+        }
+    }
+
+    /// Short summary of what changed since `before`, e.g. "+3 blocks ·
+    /// balance +1.5 BNT · mining stopped".
+    pub fn changes_since(&self, before: &Checkpoint) -> String {
+        let now = self.checkpoint();
+        let mut parts = vec![];
+        if before.height > 0 && now.height != before.height {
+            let n = now.height as i64 - before.height as i64;
+            parts.push(format!("{:+} block{}", n, if n.abs() == 1 { "" } else { "s" }));
+        }
+        if let (Some(old), Some(new)) = (before.balance, now.balance)
+            && old != new
+        {
+            let sign = if new > old { '+' } else { '-' };
+            parts.push(format!("balance {}{}", sign, self.fmt_bnt(new.abs_diff(old))));
+        }
+        if let (Some(old), Some(new)) = (before.mempool, now.mempool)
+            && old != new
+        {
+            parts.push(format!("mempool {:+} txs", new as i64 - old as i64));
+        }
+        if let (Some(old), Some(new)) = (before.mining, now.mining)
+            && old != new
+        {
+            parts.push(format!("mining {}", if new { "started" } else { "stopped" }));
+        }
+        if parts.is_empty() {
+            "nothing changed".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+
+    /// Live check of a send amount against the current spendable balance.
+    /// Evaluated every frame so a balance update mid-edit is picked up.
+    pub fn send_amount_warning(&self, amount: &str) -> Option<String> {
+        let atomic = types::parse_bnt_amount(amount)?;
+        let balance = self.balance.as_ref()?;
+        (atomic > balance.spendable).then(|| {
+            format!("Exceeds spendable balance ({})", types::format_bnt(balance.spendable))
+        })
+    }
+
+    /// Seconds since `last_tick`, and whether that's more than two poll
+    /// intervals (i.e. at least one fetch has been missed).
+    pub fn staleness(&self, last_tick: Option<u64>, interval: u64) -> Option<(u64, bool)> {
+        let ticks = self.tick_count.saturating_sub(last_tick?);
+        Some((ticks * TICK_MS / 1000, ticks > interval.saturating_mul(2)))
+    }
+
+    /// Record an event in the in-app log (and the file log).
+    pub fn event(&mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        crate::log::write(&msg);
+        self.push_event(msg);
+    }
+
+    /// Record an event that names wallet details (addresses, amounts,
+    /// txids) in the in-app log only. bntui.log is plaintext and kept
+    /// regardless of `tx_log` and `[storage] encrypt`.
+    pub fn wallet_event(&mut self, msg: impl Into<String>) {
+        self.push_event(msg.into());
+    }
+
+    fn push_event(&mut self, msg: String) {
+        if self.events.len() >= EVENT_LOG_CAP {
+            self.events.pop_front();
+        }
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.events.push_back(Event { time, msg });
+    }
+
     pub fn set_flash(&mut self, msg: String) {
         self.flash_message = Some(FlashMessage {
             text: msg,
@@ -181,32 +1233,519 @@ impl App {
         });
     }
 
+    /// Index of the loaded block containing `txid`.
+    pub fn find_tx_block(&self, txid: &str) -> Option<usize> {
+        self.chain_blocks
+            .iter()
+            .rposition(|b| b.transactions.iter().any(|tx| tx.hash == txid))
+    }
+
+    /// Select block `height`. One below the loaded range is reached by
+    /// extending the backfill down to it, and selected when it arrives.
+    /// Returns what to flash.
+    pub fn goto_block(&mut self, height: u64) -> Result<String, String> {
+        let tip = self
+            .status
+            .as_ref()
+            .map_or(0, |s| s.chain_height)
+            .max(self.chain_blocks.last().map_or(0, |b| b.height));
+        if height > tip {
+            return Err(format!("Above the tip (#{})", tip));
+        }
+        if let Some(idx) = self.chain_blocks.iter().position(|b| b.height == height) {
+            self.selected = idx;
+            self.follow_tip = idx + 1 == self.chain_blocks.len();
+            return Ok(format!("Block #{}", height));
+        }
+        if let Some(prune) = self.prune_height
+            && height < prune
+        {
+            return Err(format!("Pruned: the node has no blocks below #{}", prune));
+        }
+        // a gap left by a failed fetch can't be filled by backfilling
+        let oldest = match self.chain_blocks.first() {
+            Some(b) if !self.replay && height < b.height => b.height,
+            _ => return Err("Not loaded".to_string()),
+        };
+        let floor = height.saturating_sub(GOTO_CONTEXT);
+        if tip - floor + 1 > self.max_blocks {
+            return Err(format!("Too far back; at most {} blocks are kept", self.max_blocks));
+        }
+        match self.backfill {
+            Some(ref mut fill) => fill.floor = fill.floor.min(floor),
+            None => {
+                self.backfill = Some(Backfill {
+                    next: oldest.saturating_sub(1),
+                    floor,
+                    misses: 0,
+                })
+            }
+        }
+        self.restore_height = Some(height);
+        self.follow_tip = false;
+        Ok(format!("Loading back to #{}…", height))
+    }
+
+    /// Position of the highlighted tx within the selected block.
+    pub fn highlighted_tx(&self) -> Option<(&types::BlockResponse, usize)> {
+        let block = self.chain_blocks.get(self.selected)?;
+        let id = self.highlight_txid.as_deref()?;
+        let idx = block.transactions.iter().position(|tx| tx.hash == id)?;
+        Some((block, idx))
+    }
+
+    /// Move the tx cursor one transaction down (or up) the selected block's
+    /// list, starting from the top of the visible page when nothing there is
+    /// highlighted yet.
+    pub fn move_tx_cursor(&mut self, down: bool) {
+        let next = match self.highlighted_tx() {
+            Some((block, i)) if down => (i + 1).min(block.transactions.len() - 1),
+            Some((_, i)) => i.saturating_sub(1),
+            None => self.tx_scroll(),
+        };
+        if let Some(tx) = self
+            .chain_blocks
+            .get(self.selected)
+            .and_then(|b| b.transactions.get(next))
+        {
+            self.highlight_txid = Some(tx.hash.clone());
+        }
+    }
+
+    /// Where the selected block's tx list starts: the PageUp/PageDown offset
+    /// if it was scrolled, else the top.
+    pub fn tx_scroll(&self) -> usize {
+        match self.chain_blocks.get(self.selected) {
+            Some(block) if block.height == self.tx_scroll_height => self.tx_scroll,
+            _ => 0,
+        }
+    }
+
+    /// Scroll the selected block's tx list a page down (or up), stopping
+    /// once its last transaction is on screen. Drops the tx cursor, which
+    /// would otherwise pull the list back to it.
+    pub fn scroll_txs(&mut self, down: bool) {
+        let Some(block) = self.chain_blocks.get(self.selected) else {
+            return;
+        };
+        let start = self.tx_scroll();
+        let page = self.tx_list_rows.saturating_sub(1).max(1);
+        let max = block.transactions.len().saturating_sub(self.tx_list_rows);
+        self.tx_scroll = if down { (start + page).min(max) } else { start.saturating_sub(page) };
+        self.tx_scroll_height = block.height;
+        self.highlight_txid = None;
+    }
+
+    /// Txids sent this session that don't appear in any loaded block yet.
+    pub fn pending_txids(&self) -> Vec<&str> {
+        let confirmed: std::collections::HashSet<&str> = self
+            .chain_blocks
+            .iter()
+            .flat_map(|b| b.transactions.iter().map(|tx| tx.hash.as_str()))
+            .collect();
+        self.tx_history
+            .iter()
+            .map(|e| e.txid.as_str())
+            .filter(|t| *t != "sent" && !confirmed.contains(t))
+            .collect()
+    }
+
+    /// Record a send for this session, and in `~/.bntui/tx.log` unless
+    /// `tx_log` is turned off.
     pub fn log_tx(&mut self, txid: &str, address: &str, amount: u64) {
-        self.tx_history.push(txid.to_string());
-        if let Ok(home) = std::env::var("HOME") {
-            let dir = std::path::PathBuf::from(home).join(".bntui");
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.tx_history.push(TxHistoryEntry {
+            timestamp,
+            txid: txid.to_string(),
+            address: address.to_string(),
+            amount,
+        });
+        if self.config.wallet.tx_log
+            && let Some(dir) = bntui_dir()
+        {
             let _ = std::fs::create_dir_all(&dir);
-            let log_path = dir.join("tx.log");
-            use std::io::Write;
-            if let Ok(mut f) = std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(log_path)
-            {
-                let ts = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.as_secs())
-                    .unwrap_or(0);
-                let _ = writeln!(f, "{} {} {} {}", ts, txid, address, amount);
+            let line = format!("{} {} {} {}", timestamp, txid, address, amount);
+            if let Err(e) = crate::crypt::append_line(&dir.join("tx.log"), &line, self.cipher.as_ref()) {
+                crate::log::write(&format!("tx.log: {}", e));
             }
         }
+        if self.shows_history() {
+            self.load_tx_log();
+        }
     }
 
-    pub fn update_flash(&mut self) {
-        if let Some(ref flash) = self.flash_message {
-            if !flash.persistent && self.tick_count - flash.created > 90 {
-                self.flash_message = None;
+    /// Read `~/.bntui/tx.log` for the history view. A missing file is an
+    /// empty history; lines that don't parse are skipped. With `tx_log`
+    /// off the view lists this session's sends instead.
+    pub fn load_tx_log(&mut self) {
+        if !self.config.wallet.tx_log {
+            self.tx_log_entries = Ok(self.tx_history.iter().rev().cloned().collect());
+            self.tx_log_selected = self.tx_log_selected.min(self.tx_history.len().saturating_sub(1));
+            return;
+        }
+        let Some(dir) = bntui_dir() else {
+            self.tx_log_entries = Ok(vec![]);
+            return;
+        };
+        self.tx_log_entries = crate::crypt::read(&dir.join("tx.log"), self.cipher.as_ref()).map(|text| {
+            let mut entries: Vec<_> = text
+                .unwrap_or_default()
+                .lines()
+                .filter_map(TxHistoryEntry::parse)
+                .collect();
+            entries.reverse();
+            entries
+        });
+        let len = self.tx_log_entries.as_ref().map_or(0, |e| e.len());
+        self.tx_log_selected = self.tx_log_selected.min(len.saturating_sub(1));
+    }
+
+    /// Txid of the highlighted history row.
+    pub fn selected_tx_log_txid(&self) -> Option<&str> {
+        let entries = self.tx_log_entries.as_ref().ok()?;
+        entries.get(self.tx_log_selected).map(|e| e.txid.as_str())
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            current_view: self.current_view,
+            tick_count: self.tick_count,
+            selected: self.selected,
+            grid_scroll_offset: self.grid_scroll_offset,
+            follow_tip: self.follow_tip,
+            split_view: self.split_view,
+            status: self.status.clone(),
+            mempool: self.mempool.clone(),
+            balance: self.balance.clone(),
+            mining: self.mining.clone(),
+            chain_blocks: self.chain_blocks.clone(),
+            prune_height: self.prune_height,
+            mempool_history: self.mempool_history.clone(),
+            mempool_size_history: self.mempool_size_history.clone(),
+            mempool_fee_history: self.mempool_fee_history.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snap: Snapshot) {
+        self.current_view = snap.current_view;
+        self.tick_count = snap.tick_count;
+        self.grid_scroll_offset = snap.grid_scroll_offset;
+        self.follow_tip = snap.follow_tip;
+        self.split_view = snap.split_view;
+        self.status = snap.status;
+        self.mempool = snap.mempool;
+        self.balance = snap.balance;
+        self.mining = snap.mining;
+        self.block_cubes = snap.chain_blocks.iter().map(|_| cube::SpinCube::new()).collect();
+        self.chain_blocks = snap.chain_blocks;
+        self.prune_height = snap.prune_height;
+        self.selected = snap.selected.min(self.chain_blocks.len().saturating_sub(1));
+        self.mempool_history = snap.mempool_history;
+        self.mempool_size_history = snap.mempool_size_history;
+        self.mempool_fee_history = snap.mempool_fee_history;
+    }
+
+    /// Dump a snapshot to `~/.bntui/snapshot-<unix time>.json`.
+    pub fn write_snapshot(&self) -> Result<std::path::PathBuf, String> {
+        let text = serde_json::to_string_pretty(&self.snapshot()).map_err(|e| e.to_string())?;
+        write_export("snapshot", "json", &text)
+    }
+
+    /// Dump the difficulty series behind the dashboard chart (every loaded
+    /// block) to `~/.bntui/difficulty-<unix time>.csv`.
+    pub fn write_difficulty_csv(&self) -> Result<std::path::PathBuf, String> {
+        if self.chain_blocks.is_empty() {
+            return Err("no blocks loaded".to_string());
+        }
+        let mut text = String::from("height,timestamp,difficulty\n");
+        for block in &self.chain_blocks {
+            text.push_str(&format!("{},{},{}\n", block.height, block.timestamp, block.difficulty));
+        }
+        write_export("difficulty", "csv", &text)
+    }
+
+    /// Dump every loaded block's height, timestamp, tx count, difficulty,
+    /// reward (atomic units) and hash to `blocks-<unix time>.csv` or
+    /// `.json` in the current directory, oldest first.
+    pub fn write_blocks_export(&self) -> Result<std::path::PathBuf, String> {
+        if self.chain_blocks.is_empty() {
+            return Err("no blocks loaded".to_string());
+        }
+        let text = match self.export_format {
+            ExportFormat::Csv => {
+                let mut text = String::from("height,timestamp,tx_count,difficulty,reward,hash\n");
+                for b in &self.chain_blocks {
+                    text.push_str(&format!(
+                        "{},{},{},{},{},{}\n",
+                        b.height, b.timestamp, b.tx_count, b.difficulty, b.reward, b.hash
+                    ));
+                }
+                text
+            }
+            ExportFormat::Json => {
+                #[derive(serde::Serialize)]
+                struct Row<'a> {
+                    height: u64,
+                    timestamp: u64,
+                    tx_count: u32,
+                    difficulty: u64,
+                    reward: u64,
+                    hash: &'a str,
+                }
+                let rows: Vec<Row> = self
+                    .chain_blocks
+                    .iter()
+                    .map(|b| Row {
+                        height: b.height,
+                        timestamp: b.timestamp,
+                        tx_count: b.tx_count,
+                        difficulty: b.difficulty,
+                        reward: b.reward,
+                        hash: &b.hash,
+                    })
+                    .collect();
+                serde_json::to_string_pretty(&rows).map_err(|e| e.to_string())?
             }
+        };
+        let ext = match self.export_format {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        };
+        let dir = std::env::current_dir().map_err(|e| e.to_string())?;
+        write_timestamped(&dir, "blocks", ext, &text)
+    }
+
+    /// Restore persisted UI state. A missing or malformed file leaves the
+    /// defaults untouched.
+    pub fn load_state(&mut self) {
+        let Some(path) = bntui_dir().map(|d| d.join("state.json")) else {
+            return;
+        };
+        let text = match crate::crypt::read(&path, self.cipher.as_ref()) {
+            Ok(Some(text)) => text,
+            Ok(None) => return,
+            Err(e) => {
+                crate::log::write(&format!("state.json: {}", e));
+                return;
+            }
+        };
+        let Ok(state) = serde_json::from_str::<SavedState>(&text) else {
+            return;
+        };
+        if let Some(view @ 1..=4) = state.view {
+            self.current_view = view;
+        }
+        if let Some(style) = state.plasma.as_deref().and_then(PlasmaStyle::from_label) {
+            self.plasma_style = style;
+        }
+        self.mine_threads = state.mine_threads.filter(|&n| n >= 1);
+        self.mined_by_us = state.mined.into_iter().collect();
+        self.restore_height = state.selected_height;
+    }
+
+    /// Select the block saved in state.json once it has been loaded; given
+    /// up on when the backfill finishes without reaching it.
+    pub fn restore_selection(&mut self) {
+        let Some(height) = self.restore_height else {
+            return;
+        };
+        if let Some(idx) = self.chain_blocks.iter().position(|b| b.height == height) {
+            self.selected = idx;
+            self.follow_tip = idx + 1 == self.chain_blocks.len();
+            self.restore_height = None;
+        } else if self.backfill.is_none() {
+            self.restore_height = None;
+        }
+    }
+
+    pub fn save_state(&self) {
+        let Some(dir) = bntui_dir() else {
+            return;
+        };
+        let state = SavedState {
+            view: Some(self.current_view),
+            plasma: Some(self.plasma_style.label().to_string()),
+            mine_threads: self.mine_threads,
+            mined: self.mined_by_us.iter().copied().collect(),
+            selected_height: self
+                .chain_blocks
+                .get(self.selected)
+                .filter(|_| self.shows_grid() && !self.follow_tip)
+                .map(|b| b.height),
+        };
+        let _ = std::fs::create_dir_all(&dir);
+        if let Ok(text) = serde_json::to_string_pretty(&state) {
+            let _ = crate::crypt::write(&dir.join("state.json"), &text, self.cipher.as_ref());
+        }
+    }
+
+    /// Hold a newly arrived block (see `display.new_block_hold`), unless
+    /// one is already held.
+    pub fn hold_new_block(&mut self, height: u64) {
+        let secs = self.config.display.new_block_hold;
+        if secs > 0 && self.held_block.is_none() {
+            self.held_block = Some((height, self.tick_count + secs * 1000 / TICK_MS));
+        }
+    }
+
+    /// End the hold, catching up with the tip if following it.
+    pub fn release_held_block(&mut self) {
+        if self.held_block.take().is_some() && self.follow_tip && !self.chain_blocks.is_empty() {
+            self.selected = self.chain_blocks.len() - 1;
+        }
+    }
+
+    pub fn update_held_block(&mut self) {
+        if self.held_block.is_some_and(|(_, until)| self.tick_count >= until) {
+            self.release_held_block();
+        }
+    }
+
+    pub fn update_flash(&mut self) {
+        if let Some(ref flash) = self.flash_message
+            && !flash.persistent
+            && self.tick_count - flash.created > 90
+        {
+            self.flash_message = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, PREVIOUS_ADDRESSES};
+
+    #[test]
+    fn block_time_ratio_follows_the_target() {
+        let mut app = App::new();
+        app.target_block_time_secs = 60;
+        assert_eq!(app.block_time_ratio(30.0), 0.5);
+        assert_eq!(app.block_time_ratio(60.0), 1.0);
+        assert_eq!(app.block_time_ratio(300.0), 5.0);
+
+        app.target_block_time_secs = 600;
+        assert_eq!(app.block_time_ratio(300.0), 0.5);
+        assert_eq!(app.block_time_ratio(600.0), 1.0);
+    }
+
+    #[test]
+    fn next_block_progress_marks_the_target_at_half() {
+        let mut app = App::new();
+        app.target_block_time_secs = 60;
+        assert_eq!(app.next_block_progress(0.0), 0.0);
+        assert_eq!(app.next_block_progress(30.0), 0.25);
+        assert_eq!(app.next_block_progress(60.0), 0.5);
+        // full at twice the target, and no further
+        assert_eq!(app.next_block_progress(120.0), 1.0);
+        assert_eq!(app.next_block_progress(300.0), 1.0);
+
+        app.target_block_time_secs = 600;
+        assert_eq!(app.next_block_progress(300.0), 0.25);
+        assert_eq!(app.next_block_progress(600.0), 0.5);
+    }
+
+    #[test]
+    fn mining_luck_counts_the_current_run_only() {
+        let mining = |running, hash_count, blocks_found| crate::types::MiningStatus {
+            running,
+            threads: 1,
+            hashrate: 0.0,
+            hash_count,
+            blocks_found,
+            started_at: None,
+        };
+        let mut app = App::new();
+        app.chain_blocks.push(crate::types::BlockResponse {
+            height: 1,
+            hash: String::new(),
+            timestamp: 0,
+            difficulty: 100_000,
+            tx_count: 0,
+            confirmations: 1,
+            reward: 0,
+            transactions: vec![],
+        });
+
+        // a lifetime of 50M hashes and 7 finds before this run
+        app.set_mining(mining(true, 50_000_000, 7));
+        assert_eq!(app.mining_luck(), None);
+        app.set_mining(mining(true, 51_000_000, 12));
+        assert_eq!(app.mining_luck(), Some(50.0));
+
+        // stopping ends the run; the next one starts from its own counters
+        app.set_mining(mining(false, 51_000_000, 12));
+        assert_eq!(app.mining_luck(), None);
+        app.set_mining(mining(true, 51_000_000, 12));
+        app.set_mining(mining(true, 51_200_000, 2 + 12));
+        assert_eq!(app.mining_luck(), Some(100.0));
+
+        // a restarted daemon counts from zero again
+        app.set_mining(mining(true, 100_000, 0));
+        app.set_mining(mining(true, 300_000, 1));
+        assert_eq!(app.mining_luck(), Some(50.0));
+    }
+
+    #[test]
+    fn trimming_keeps_the_grid_on_the_same_rows() {
+        let mut app = App::new();
+        app.chain_blocks = (0..120)
+            .map(|height| crate::types::BlockResponse {
+                height,
+                hash: String::new(),
+                timestamp: 0,
+                difficulty: 1,
+                tx_count: 0,
+                confirmations: 1,
+                reward: 0,
+                transactions: vec![],
+            })
+            .collect();
+        app.max_blocks = 100;
+        app.blocks_per_row = 10;
+        app.selected = 95;
+        app.grid_scroll_offset = 7;
+
+        app.trim_blocks();
+        assert_eq!(app.chain_blocks.len(), 100);
+        assert_eq!(app.chain_blocks[app.selected].height, 95);
+        assert_eq!(app.grid_scroll_offset, 5);
+
+        // never above the first row
+        app.chain_blocks.extend(app.chain_blocks.clone());
+        app.trim_blocks();
+        assert_eq!(app.grid_scroll_offset, 0);
+    }
+
+    #[test]
+    fn rotation_keeps_the_last_few_addresses() {
+        let mut app = App::new();
+        app.rotate_address("a0".to_string());
+        assert!(app.previous_addresses.is_empty());
+        for i in 1..=7 {
+            app.rotate_address(format!("a{}", i));
+        }
+        assert_eq!(app.receive_address(0), Some("a7"));
+        assert_eq!(app.previous_addresses, ["a6", "a5", "a4", "a3", "a2"]);
+        assert_eq!(app.receive_address(1), Some("a6"));
+        assert_eq!(app.receive_address(PREVIOUS_ADDRESSES + 1), None);
+    }
+
+    #[test]
+    fn older_daemons_skip_the_endpoints_they_lack() {
+        let mut app = App::new();
+        app.set_daemon_version(Some("v0.2.4".to_string()));
+        assert!(app.peers_supported);
+        assert!(!app.mempool_txs_supported);
+        assert!(!app.fee_estimate_supported);
+
+        // no version, or one that doesn't parse: probe and wait for a 404
+        for version in [None, Some("nightly".to_string())] {
+            let mut app = App::new();
+            app.set_daemon_version(version);
+            assert!(app.peers_supported && app.mempool_txs_supported && app.fee_estimate_supported);
         }
     }
 }
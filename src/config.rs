@@ -0,0 +1,317 @@
+use serde::Deserialize;
+
+/// User settings from `~/.bntui/config.toml`. Every key is optional; a
+/// missing file yields the defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub mempool: MempoolAlertConfig,
+    pub display: DisplayConfig,
+    pub wallet: WalletConfig,
+    pub explorer: ExplorerConfig,
+    pub reorg: ReorgConfig,
+    pub storage: StorageConfig,
+    pub fiat: FiatConfig,
+    pub connection: ConnectionConfig,
+    pub theme: ThemeConfig,
+}
+
+/// `[mempool]` — when to treat the mempool as congested.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MempoolAlertConfig {
+    pub alert_count: u32,
+    pub alert_bytes: u64,
+    /// flash once when the threshold is crossed
+    pub alert_flash: bool,
+}
+
+impl Default for MempoolAlertConfig {
+    fn default() -> Self {
+        Self {
+            alert_count: 1000,
+            alert_bytes: 1_000_000,
+            alert_flash: true,
+        }
+    }
+}
+
+/// `[display]` — how amounts are formatted in the panels.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// fixed number of decimal places (0–8); unset trims trailing zeros
+    pub precision: Option<i64>,
+    /// points kept per sparkline history
+    pub history_len: Option<i64>,
+    /// most blocks kept in memory; older ones are dropped
+    pub max_blocks: Option<u64>,
+    /// seconds a new block is held in place and blinks in the grid before
+    /// following the tip moves on; 0 turns it off
+    pub new_block_hold: u64,
+}
+
+impl DisplayConfig {
+    pub fn precision(&self) -> Option<u8> {
+        self.precision.map(|p| p.clamp(0, 8) as u8)
+    }
+
+    pub fn history_len(&self) -> usize {
+        self.history_len.map_or(crate::app::DEFAULT_HISTORY_LEN, |n| {
+            n.clamp(crate::app::MIN_HISTORY_LEN as i64, crate::app::MAX_HISTORY_LEN as i64) as usize
+        })
+    }
+}
+
+/// `[wallet]` — receive address and send record behavior.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WalletConfig {
+    /// switch to a fresh address whenever the balance goes up
+    pub auto_rotate_address: bool,
+    /// append each send to `~/.bntui/tx.log`
+    pub tx_log: bool,
+    /// sends above this many BNT must be confirmed by typing CONFIRM
+    pub max_send: Option<f64>,
+}
+
+impl Default for WalletConfig {
+    fn default() -> Self {
+        Self {
+            auto_rotate_address: false,
+            tx_log: true,
+            max_send: None,
+        }
+    }
+}
+
+impl WalletConfig {
+    /// `max_send` in atomic units; unset or not positive means no limit.
+    pub fn max_send_atomic(&self) -> Option<u64> {
+        self.max_send
+            .filter(|v| *v > 0.0)
+            .map(|v| (v * 100_000_000.0).round() as u64)
+    }
+}
+
+/// `[explorer]` — block explorer URL templates.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExplorerConfig {
+    /// `{height}` and `{hash}` are replaced with the block's height and hash
+    pub block_url: String,
+    /// `{txid}` is replaced with the transaction id
+    pub tx_url: String,
+}
+
+impl Default for ExplorerConfig {
+    fn default() -> Self {
+        Self {
+            block_url: "https://explorer.blocknetcrypto.com/block/{height}".to_string(),
+            tx_url: "https://explorer.blocknetcrypto.com/tx/{txid}".to_string(),
+        }
+    }
+}
+
+impl ExplorerConfig {
+    pub fn block(&self, height: u64, hash: &str) -> String {
+        self.block_url
+            .replace("{height}", &height.to_string())
+            .replace("{hash}", hash)
+    }
+
+    /// Whether `template` names the block somehow; without a placeholder
+    /// every block would open the same page.
+    pub fn is_block_template(template: &str) -> bool {
+        template.contains("{height}") || template.contains("{hash}")
+    }
+
+    pub fn tx(&self, txid: &str) -> String {
+        self.tx_url.replace("{txid}", txid)
+    }
+}
+
+/// `[reorg]` — when a chain reorganization deserves more than a flash.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ReorgConfig {
+    /// reorgs replacing at least this many blocks raise a persistent banner
+    pub alert_depth: u64,
+}
+
+impl Default for ReorgConfig {
+    fn default() -> Self {
+        Self { alert_depth: 3 }
+    }
+}
+
+/// `[connection]` — connection reuse for the API client; mostly matters
+/// for a remote daemon, where a fresh TCP/TLS handshake per poll adds up.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    /// seconds an idle connection is kept for the next poll
+    pub pool_idle_secs: u64,
+    /// idle connections kept per host
+    pub pool_max_idle: usize,
+    /// seconds before a request to the daemon is given up
+    pub timeout_secs: u64,
+    /// seconds before connecting to the daemon is given up
+    pub connect_timeout_secs: u64,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        let api = crate::api::ApiConfig::default();
+        Self {
+            pool_idle_secs: api.pool_idle_timeout.as_secs(),
+            pool_max_idle: api.pool_max_idle_per_host,
+            timeout_secs: api.timeout.as_secs(),
+            connect_timeout_secs: api.connect_timeout.as_secs(),
+        }
+    }
+}
+
+/// `[storage]` — how bntui's own files in `~/.bntui` are kept.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// encrypt state.json, tx.log, mined.log and addresses.toml with a passphrase asked at startup
+    pub encrypt: bool,
+}
+
+/// `[fiat]` — optional fiat value next to the balance. Off unless a rate
+/// or URL is set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FiatConfig {
+    /// fixed price of 1 BNT
+    pub rate: Option<f64>,
+    /// fetch the price from here instead
+    pub url: Option<String>,
+    /// seconds between fetches from `url`
+    pub refresh_secs: u64,
+    /// prefix for the converted amount
+    pub symbol: String,
+}
+
+impl Default for FiatConfig {
+    fn default() -> Self {
+        Self {
+            rate: None,
+            url: None,
+            refresh_secs: 60,
+            symbol: "$".to_string(),
+        }
+    }
+}
+
+/// `[theme]` — colors as `#rrggbb`; unset or invalid keys keep the
+/// default look.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// borders, titles and highlights
+    pub primary: Option<String>,
+    /// labels and secondary text
+    pub dim: Option<String>,
+    /// mempool transaction counts and the mining progress bar
+    pub accent: Option<String>,
+    /// pending amounts, banners and other things that need a look
+    pub warning: Option<String>,
+}
+
+impl ThemeConfig {
+    /// `(key, value)` for every key that is set.
+    pub fn entries(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("primary", &self.primary),
+            ("dim", &self.dim),
+            ("accent", &self.accent),
+            ("warning", &self.warning),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_deref().map(|v| (key, v)))
+    }
+}
+
+/// Parse `#rrggbb` or `#rgb` (the `#` is optional).
+pub fn parse_hex_color(text: &str) -> Option<(u8, u8, u8)> {
+    let hex = text.trim().trim_start_matches('#');
+    if !hex.is_ascii() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        6 => Some((channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+        3 => {
+            let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+            Some((short(0)?, short(1)?, short(2)?))
+        }
+        _ => None,
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<std::path::PathBuf> {
+        crate::app::bntui_dir().map(|d| d.join("config.toml"))
+    }
+
+    /// Load the config file, falling back to defaults (with a warning on
+    /// stderr) if it can't be parsed.
+    pub fn load() -> Config {
+        let Some(path) = Self::path() else {
+            return Config::default();
+        };
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Config::default();
+        };
+        match toml::from_str::<Config>(&text) {
+            Ok(mut config) => {
+                if let Some(p) = config.display.precision
+                    && !(0..=8).contains(&p)
+                {
+                    eprintln!(
+                        "warning: {}: display.precision {} is out of range, clamping to 0–8",
+                        path.display(),
+                        p
+                    );
+                }
+                if let Some(n) = config.display.history_len
+                    && !(crate::app::MIN_HISTORY_LEN as i64..=crate::app::MAX_HISTORY_LEN as i64).contains(&n)
+                {
+                    eprintln!(
+                        "warning: {}: display.history_len {} is out of range, clamping to {}–{}",
+                        path.display(),
+                        n,
+                        crate::app::MIN_HISTORY_LEN,
+                        crate::app::MAX_HISTORY_LEN
+                    );
+                }
+                for (key, value) in config.theme.entries() {
+                    if parse_hex_color(value).is_none() {
+                        eprintln!(
+                            "warning: {}: theme.{} {:?} is not a #rrggbb color, using the default",
+                            path.display(),
+                            key,
+                            value
+                        );
+                    }
+                }
+                if !ExplorerConfig::is_block_template(&config.explorer.block_url) {
+                    eprintln!(
+                        "warning: {}: explorer.block_url {:?} has no {{height}} or {{hash}}, using the default",
+                        path.display(),
+                        config.explorer.block_url
+                    );
+                    config.explorer.block_url = ExplorerConfig::default().block_url;
+                }
+                config
+            }
+            Err(e) => {
+                eprintln!("warning: ignoring {}: {}", path.display(), e);
+                Config::default()
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Optional passphrase encryption for bntui's own files in `~/.bntui`
+//! (`state.json`, `tx.log`, `mined.log`, `addresses.toml`), enabled with `[storage] encrypt = true`.
+//!
+//! The key is Argon2id over the passphrase with a per-install salt kept in
+//! `~/.bntui/vault`, next to a known token encrypted with it so a wrong
+//! passphrase is caught at startup. Files are ChaCha20-Poly1305 with a fresh
+//! nonce per write, behind a magic prefix so plaintext files from before
+//! encryption was turned on are still readable.
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    ChaCha20Poly1305, KeyInit, Nonce,
+    aead::{Aead, AeadCore, OsRng, rand_core::RngCore},
+};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8] = b"bntui-enc1\n";
+const NONCE_LEN: usize = 12;
+const SALT_LEN: usize = 16;
+const CHECK: &[u8] = b"bntui vault";
+
+#[derive(Clone)]
+pub struct Key([u8; 32]);
+
+fn vault_path() -> Option<PathBuf> {
+    crate::app::bntui_dir().map(|d| d.join("vault"))
+}
+
+/// Whether a passphrase has been set up before.
+pub fn vault_exists() -> bool {
+    vault_path().is_some_and(|p| p.is_file())
+}
+
+fn derive(passphrase: &str, salt: &[u8]) -> Result<Key, String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("key derivation failed: {}", e))?;
+    Ok(Key(key))
+}
+
+/// Derive the key for `passphrase`, creating the vault on first use. Fails
+/// with a clear message if the passphrase doesn't match the existing vault.
+pub fn unlock(passphrase: &str) -> Result<Key, String> {
+    let path = vault_path().ok_or_else(|| "HOME is not set".to_string())?;
+    match std::fs::read(&path) {
+        Ok(data) => {
+            if data.len() < SALT_LEN {
+                return Err(format!("{} is corrupt", path.display()));
+            }
+            let (salt, check) = data.split_at(SALT_LEN);
+            let key = derive(passphrase, salt)?;
+            match decrypt(&key, check) {
+                Ok(token) if token == CHECK => Ok(key),
+                _ => Err(format!("wrong passphrase (doesn't unlock {})", path.display())),
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut salt = [0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            let key = derive(passphrase, &salt)?;
+            let mut data = salt.to_vec();
+            data.extend(encrypt(&key, CHECK)?);
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            std::fs::write(&path, data).map_err(|e| format!("can't write {}: {}", path.display(), e))?;
+            Ok(key)
+        }
+        Err(e) => Err(format!("can't read {}: {}", path.display(), e)),
+    }
+}
+
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = ChaCha20Poly1305::new((&key.0).into());
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| "encryption failed".to_string())?;
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&nonce);
+    out.extend(sealed);
+    Ok(out)
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Decrypt `data`, passing plaintext (no magic prefix) through unchanged.
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>, String> {
+    let Some(body) = data.strip_prefix(MAGIC) else {
+        return Ok(data.to_vec());
+    };
+    if body.len() < NONCE_LEN {
+        return Err("encrypted file is truncated".to_string());
+    }
+    let (nonce, sealed) = body.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new((&key.0).into())
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| "can't decrypt (wrong passphrase or corrupt file)".to_string())
+}
+
+/// Read a text file, decrypting it with `key` if it is encrypted. `None` if
+/// the file doesn't exist.
+pub fn read(path: &Path, key: Option<&Key>) -> Result<Option<String>, String> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    let plain = match key {
+        Some(key) => decrypt(key, &data)?,
+        None if is_encrypted(&data) => {
+            return Err(format!("{} is encrypted; set storage.encrypt to read it", path.display()));
+        }
+        None => data,
+    };
+    String::from_utf8(plain).map(Some).map_err(|e| e.to_string())
+}
+
+/// Write a text file, encrypted if `key` is given.
+pub fn write(path: &Path, text: &str, key: Option<&Key>) -> Result<(), String> {
+    let data = match key {
+        Some(key) => encrypt(key, text.as_bytes())?,
+        None => text.as_bytes().to_vec(),
+    };
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+/// Append a line to a log file. Encrypted logs can't be appended to in
+/// place, so with a key the whole file is rewritten; without one, an
+/// encrypted log is left alone rather than corrupted.
+pub fn append_line(path: &Path, line: &str, key: Option<&Key>) -> Result<(), String> {
+    let Some(key) = key else {
+        if std::fs::read(path).is_ok_and(|data| is_encrypted(&data)) {
+            return Err(format!("{} is encrypted; set storage.encrypt to add to it", path.display()));
+        }
+        use std::io::Write;
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| e.to_string())?;
+        return writeln!(f, "{}", line).map_err(|e| e.to_string());
+    };
+    let mut text = read(path, Some(key))?.unwrap_or_default();
+    text.push_str(line);
+    text.push('\n');
+    write(path, &text, Some(key))
+}
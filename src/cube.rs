@@ -99,12 +99,30 @@ const EDGES: [(usize, usize); 12] = [
     (3, 7), // connectors
 ];
 
+/// How the cube's edges are drawn.
+#[derive(Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum CubeStyle {
+    // one `$` per sampled point, the original look
+    #[default]
+    AsciiDollar,
+    // 2x4 dots per cell, so edges read as thin lines
+    Braille,
+    // half blocks, twice the vertical resolution of a cell
+    Block,
+    // line characters picked from each edge's slope
+    Wireframe,
+}
+
+// braille dot bits by [column][row] within a cell
+const BRAILLE_DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
 pub struct SpinCube {
     pub angle_x: f32,
     pub angle_y: f32,
     pub angle_z: f32,
     pub color: Color,
     pub frozen: bool,
+    pub style: CubeStyle,
 }
 
 impl SpinCube {
@@ -115,6 +133,7 @@ impl SpinCube {
             angle_z: 0.0,
             color: Color::White,
             frozen: true,
+            style: CubeStyle::default(),
         }
     }
 
@@ -157,20 +176,83 @@ impl Widget for &mut SpinCube {
             })
             .collect();
 
-        // draw each edge by interpolating points along it
-        for (i0, i1) in EDGES {
-            if let (Some((x0, y0)), Some((x1, y1))) = (projected[i0], projected[i1]) {
-                for s in 0..=60 {
-                    let t = s as f32 / 60.0;
-                    let px = (x0 + (x1 - x0) * t) as u16;
-                    let py = (y0 + (y1 - y0) * t) as u16;
-                    if px < area.width && py < area.height {
-                        buf[(area.x + px, area.y + py)]
-                            .set_char('$')
-                            .set_style(style);
+        let edges = EDGES.iter().filter_map(|&(i0, i1)| Some((projected[i0]?, projected[i1]?)));
+        match self.style {
+            CubeStyle::AsciiDollar => {
+                // draw each edge by interpolating points along it
+                for ((x0, y0), (x1, y1)) in edges {
+                    for s in 0..=60 {
+                        let t = s as f32 / 60.0;
+                        let px = (x0 + (x1 - x0) * t) as u16;
+                        let py = (y0 + (y1 - y0) * t) as u16;
+                        if px < area.width && py < area.height {
+                            buf[(area.x + px, area.y + py)]
+                                .set_char('$')
+                                .set_style(style);
+                        }
+                    }
+                }
+            }
+            CubeStyle::Wireframe => {
+                for ((x0, y0), (x1, y1)) in edges {
+                    let (dx, dy) = (x1 - x0, y1 - y0);
+                    // cells are about twice as tall as wide
+                    let (rise, run) = (dy.abs() * 2.0, dx.abs());
+                    let ch = if rise < run * 0.5 {
+                        '─'
+                    } else if rise > run * 2.0 {
+                        '│'
+                    } else if (dx > 0.0) == (dy > 0.0) {
+                        '╲'
+                    } else {
+                        '╱'
+                    };
+                    let steps = (dx.abs().max(dy.abs()).ceil() as usize).max(1);
+                    for s in 0..=steps {
+                        let t = s as f32 / steps as f32;
+                        let px = (x0 + dx * t) as u16;
+                        let py = (y0 + dy * t) as u16;
+                        if px < area.width && py < area.height {
+                            buf[(area.x + px, area.y + py)].set_char(ch).set_style(style);
+                        }
                     }
                 }
             }
+            CubeStyle::Braille | CubeStyle::Block => {
+                // sub-cell grid: braille has 2x4 dots per cell, half blocks 1x2
+                let (sx, sy) = if self.style == CubeStyle::Braille { (2, 4) } else { (1, 2) };
+                let mut cells = vec![0u8; area.width as usize * area.height as usize];
+                for ((x0, y0), (x1, y1)) in edges {
+                    let (dx, dy) = ((x1 - x0) * sx as f32, (y1 - y0) * sy as f32);
+                    let steps = (dx.abs().max(dy.abs()).ceil() as usize).max(1) * 2;
+                    for s in 0..=steps {
+                        let t = s as f32 / steps as f32;
+                        let fx = (x0 + (x1 - x0) * t) * sx as f32;
+                        let fy = (y0 + (y1 - y0) * t) * sy as f32;
+                        if fx < 0.0 || fy < 0.0 {
+                            continue;
+                        }
+                        let (fx, fy) = (fx as usize, fy as usize);
+                        let (cx, cy) = (fx / sx, fy / sy);
+                        if cx < area.width as usize && cy < area.height as usize {
+                            let bit = if self.style == CubeStyle::Braille {
+                                BRAILLE_DOTS[fx % sx][fy % sy]
+                            } else {
+                                1 << (fy % sy)
+                            };
+                            cells[cy * area.width as usize + cx] |= bit;
+                        }
+                    }
+                }
+                for (i, &bits) in cells.iter().enumerate().filter(|&(_, &bits)| bits != 0) {
+                    let ch = match self.style {
+                        CubeStyle::Braille => char::from_u32(0x2800 + bits as u32).unwrap_or(' '),
+                        _ => ['▀', '▄', '█'][bits as usize - 1],
+                    };
+                    let (px, py) = ((i % area.width as usize) as u16, (i / area.width as usize) as u16);
+                    buf[(area.x + px, area.y + py)].set_char(ch).set_style(style);
+                }
+            }
         }
     }
 }
@@ -0,0 +1,65 @@
+//! `--expose`: hand the status and mining data bntui has already polled to
+//! anything that connects, as one line of JSON, so other dashboards can read
+//! it without querying the node again. Read-only; whatever is sent to the
+//! socket is ignored.
+//!
+//! ```json
+//! {"updated": 1700000000, "status": {...} | null, "mining": {...} | null}
+//! ```
+//!
+//! `status` and `mining` are the daemon's `/api/status` and `/api/mining`
+//! responses as bntui parsed them; `updated` is when they were last copied
+//! in (unix seconds).
+
+use std::io::Write;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+
+use crate::app::App;
+
+pub struct Exposer {
+    latest: Arc<Mutex<String>>,
+}
+
+/// Parse `--expose`: a bare port binds to 127.0.0.1, otherwise `host:port`.
+pub fn parse_addr(s: &str) -> Result<SocketAddr, String> {
+    if let Ok(port) = s.parse::<u16>() {
+        return Ok(SocketAddr::from(([127, 0, 0, 1], port)));
+    }
+    s.parse()
+        .map_err(|_| format!("expected a port or host:port, got '{}'", s))
+}
+
+impl Exposer {
+    /// Bind `addr` and serve from a background thread.
+    pub fn start(addr: SocketAddr) -> Result<Exposer, String> {
+        let listener = TcpListener::bind(addr).map_err(|e| format!("can't listen on {}: {}", addr, e))?;
+        let latest = Arc::new(Mutex::new("{}".to_string()));
+        let shared = Arc::clone(&latest);
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let body = shared.lock().map(|s| s.clone()).unwrap_or_default();
+                // a stalled reader must not hold up the next client
+                let _ = stream.set_write_timeout(Some(std::time::Duration::from_secs(1)));
+                let _ = writeln!(stream, "{}", body);
+            }
+        });
+        Ok(Exposer { latest })
+    }
+
+    /// Copy the current status and mining data in for the next client.
+    pub fn update(&self, app: &App) {
+        let updated = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let body = serde_json::json!({
+            "updated": updated,
+            "status": app.status,
+            "mining": app.mining,
+        })
+        .to_string();
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = body;
+        }
+    }
+}
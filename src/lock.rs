@@ -0,0 +1,89 @@
+//! `bntui.lock` in the Blocknet directory: which bntui autostarted the
+//! embedded daemon there, and on which port. A second bntui on the same
+//! directory connects to that daemon instead of spawning another one next
+//! to it. The file is removed when the owner exits; one left behind by a
+//! crash is noticed because its PID is gone, and taken over.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub struct DaemonLock {
+    path: PathBuf,
+}
+
+pub enum Acquire {
+    Acquired(DaemonLock),
+    /// another live bntui manages a daemon listening on `port`
+    HeldBy { pid: u32, port: u16 },
+}
+
+fn lock_path(blocknet_dir: &Path) -> PathBuf {
+    blocknet_dir.join("bntui.lock")
+}
+
+/// Take the lock for a daemon about to be started on `port`.
+pub fn acquire(blocknet_dir: &Path, port: u16) -> Result<Acquire, String> {
+    let path = lock_path(blocknet_dir);
+    // one retry, after clearing a stale lock
+    for _ in 0..2 {
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut f) => {
+                writeln!(f, "{} {}", std::process::id(), port)
+                    .map_err(|e| format!("can't write {}: {}", path.display(), e))?;
+                return Ok(Acquire::Acquired(DaemonLock { path }));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                let text = std::fs::read_to_string(&path).unwrap_or_default();
+                let mut fields = text.split_whitespace().map(|s| s.parse().ok());
+                if let (Some(Some(pid)), Some(Some(port))) = (fields.next(), fields.next())
+                    && pid != std::process::id()
+                    && process_alive(pid)
+                {
+                    return Ok(Acquire::HeldBy {
+                        pid,
+                        port: port as u16,
+                    });
+                }
+                crate::log::write(&format!("removing stale {}: {:?}", path.display(), text.trim()));
+                let _ = std::fs::remove_file(&path);
+            }
+            Err(e) => return Err(format!("can't create {}: {}", path.display(), e)),
+        }
+    }
+    Err(format!("{} keeps reappearing", path.display()))
+}
+
+impl Drop for DaemonLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn process_alive(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        Path::new("/proc").join(pid.to_string()).exists()
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    }
+    #[cfg(windows)]
+    {
+        std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains(&pid.to_string()))
+    }
+    // no way to check elsewhere: treat the lock as held rather than take it
+    // from a daemon that may still be running
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = pid;
+        true
+    }
+}
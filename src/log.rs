@@ -0,0 +1,21 @@
+use std::io::Write;
+
+/// Append a timestamped line to `~/.bntui/bntui.log`. Failures are ignored;
+/// logging must never interfere with the UI.
+pub fn write(msg: &str) {
+    let Some(dir) = crate::app::bntui_dir() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(&dir);
+    if let Ok(mut f) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("bntui.log"))
+    {
+        let ts = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let _ = writeln!(f, "{} {}", ts, msg);
+    }
+}
@@ -1,12 +1,20 @@
-use clap::Parser;
-use crossterm::event::{Event, KeyCode, KeyEventKind};
+use clap::{CommandFactory, FromArgMatches, Parser, parser::ValueSource};
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use futures::stream::{self, StreamExt};
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+mod addressbook;
 mod api;
 mod app;
+mod config;
+mod crypt;
 mod cube;
+mod expose;
+mod lock;
+mod log;
+mod theme;
 mod types;
 mod ui;
 
@@ -27,6 +35,9 @@ If no directory is given, bntui will use this resolution order:
        Linux:   ~/.blocknet
        Windows: %APPDATA%\\Blocknet
 
+If <dir>/data/daemon.json exists (host/port/cookie keys) and no
+--host/--port/--cookie is given, bntui connects where it points.
+
 If host=localhost and port=8332 and no cookie exists, bntui will auto-start
 the embedded Blocknet daemon with:
   --api 127.0.0.1:8332 --daemon --data <dir>/data --wallet <dir>/wallet.dat")]
@@ -45,11 +56,237 @@ struct Cli {
     /// Path to API cookie file (default: {blocknet_dir}/data/api.cookie)
     #[arg(long)]
     cookie: Option<String>,
+
+    /// Daemon API as one URL, http(s)://host[:port][#cookie=<path>], instead
+    /// of --host/--port [env: BNTUI_API_URL]
+    #[arg(long, value_name = "URL", conflicts_with_all = ["host", "port"])]
+    url: Option<String>,
+
+    /// Connect to --host/--port over HTTPS, for a node behind TLS. Also turns
+    /// off the embedded daemon autostart, which only speaks HTTP
+    #[arg(long, conflicts_with = "url")]
+    tls: bool,
+
+    /// Don't verify the node's TLS certificate, for a self-signed one
+    #[arg(long)]
+    insecure: bool,
+
+    /// Send this API token instead of reading a cookie file. Other users can
+    /// see it in `ps`; prefer --token-file or the env var [env: BNTUI_API_TOKEN]
+    #[arg(long, value_name = "TOKEN", conflicts_with = "cookie")]
+    token: Option<String>,
+
+    /// Send the API token read from this file instead of reading a cookie file
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["cookie", "token"])]
+    token_file: Option<PathBuf>,
+
+    /// Send API requests through this HTTP proxy [default: proxy env vars,
+    /// for remote hosts only]
+    #[arg(long, value_name = "URL", conflicts_with = "no_proxy")]
+    proxy: Option<String>,
+
+    /// Never use a proxy, even for remote hosts
+    #[arg(long)]
+    no_proxy: bool,
+
+    /// Target block time in seconds, for chains/testnets that differ from
+    /// Blocknet's
+    #[arg(
+        long,
+        visible_alias = "block-target",
+        value_name = "SECS",
+        default_value_t = app::DEFAULT_TARGET_BLOCK_TIME,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    target_block_time: u64,
+
+    /// Seconds between status polls (height, sync, new blocks), from one
+    /// 33ms tick up to a day [default: 1]
+    #[arg(long, value_name = "SECS", value_parser = parse_poll_interval)]
+    status_interval: Option<u64>,
+
+    /// Seconds between mempool, balance and mining polls, from one 33ms
+    /// tick up to a day [default: 3]
+    #[arg(long, value_name = "SECS", value_parser = parse_poll_interval)]
+    data_interval: Option<u64>,
+
+    /// Blocks to load at startup [default: two screens of grid, 100–2000]
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    blocks: Option<u64>,
+
+    /// Points kept per sparkline history (10–10000) [default: 200, or
+    /// display.history_len]
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(10..=10_000))]
+    history_len: Option<u64>,
+
+    /// Most blocks kept in memory; the oldest are dropped beyond this
+    /// [default: 5000, or display.max_blocks]
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(app::MIN_MAX_BLOCKS..))]
+    max_blocks: Option<u64>,
+
+    /// Show the balance in fiat at this fixed price per BNT
+    #[arg(long, value_name = "RATE", conflicts_with = "price_url")]
+    price: Option<f64>,
+
+    /// Fetch the price per BNT from this URL every fiat.refresh_secs (JSON
+    /// number, or an object with a price/usd/rate field)
+    #[arg(long, value_name = "URL")]
+    price_url: Option<String>,
+
+    /// Open blocks in this explorer with `v`; `{height}` and `{hash}` are
+    /// filled in [default: explorer.block_url, else explorer.blocknetcrypto.com]
+    #[arg(long, value_name = "TEMPLATE", value_parser = parse_explorer_url)]
+    explorer_url: Option<String>,
+
+    /// Serve the polled status and mining data as JSON to anyone who
+    /// connects to this port or host:port (a bare port listens on 127.0.0.1)
+    #[arg(long, value_name = "ADDR", value_parser = expose::parse_addr)]
+    expose: Option<std::net::SocketAddr>,
+
+    /// Plain ASCII output without graphics (cube, plasma, charts), for screen
+    /// readers and limited terminals
+    #[arg(long)]
+    accessible: bool,
+
+    /// File format for the blocks exported with `E` in the grid
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = app::ExportFormat::Csv)]
+    export_format: app::ExportFormat,
+
+    /// How the grid's spinning cube draws its edges
+    #[arg(long, value_enum, value_name = "STYLE", default_value_t = cube::CubeStyle::AsciiDollar)]
+    cube_style: cube::CubeStyle,
+
+    /// Don't write sends to ~/.bntui/tx.log (they're still listed for this session)
+    #[arg(long)]
+    no_tx_log: bool,
+
+    /// Refuse sends, broadcasts, mining and thread changes, and wallet
+    /// unlock/lock/creation, for watching a node you don't control
+    #[arg(long, conflicts_with = "mine")]
+    readonly: bool,
+
+    /// Show dashboard and grid side by side on wide terminals
+    #[arg(long)]
+    split: bool,
+
+    /// Autostart this embedded daemon binary instead of auto-selecting one
+    #[arg(long, value_name = "NAME")]
+    daemon_binary: Option<String>,
+
+    /// Pass ARG on to the autostarted embedded daemon (repeatable), e.g.
+    /// --daemon-arg=--testnet
+    #[arg(long = "daemon-arg", value_name = "ARG", allow_hyphen_values = true)]
+    daemon_args: Vec<String>,
+
+    /// List the embedded daemon binaries and exit
+    #[arg(long)]
+    list_daemons: bool,
+
+    /// Render a snapshot file (saved with `D`) instead of connecting
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+
+    /// With --replay: render the snapshot at every small terminal size and
+    /// report any layout that panics
+    #[arg(long, requires = "replay", hide = true)]
+    resize_stress: bool,
+
+    /// With --replay: render --frames animation frames at 120x40 into this
+    /// directory as ANSI text files (frame-0000.ans, ...)
+    #[arg(long, requires = "replay", value_name = "DIR")]
+    frames_to_file: Option<PathBuf>,
+
+    /// Number of frames for --frames-to-file (~30 per second)
+    #[arg(long, default_value_t = 150, requires = "frames_to_file")]
+    frames: u32,
+
+    /// View to start on [default: last used]
+    #[arg(long, value_enum)]
+    view: Option<StartView>,
+
+    /// Start mining on launch
+    #[arg(long)]
+    mine: bool,
+
+    /// Mining threads for --mine: a number, or `auto` for one less than the
+    /// core count [default: last explicit value, else auto]
+    #[arg(long, value_name = "N|auto", value_parser = parse_mine_threads, requires = "mine")]
+    mine_threads: Option<MineThreads>,
+}
+
+#[derive(Clone, Copy)]
+enum MineThreads {
+    Auto,
+    Fixed(u32),
+}
+
+fn parse_mine_threads(s: &str) -> Result<MineThreads, String> {
+    if s.eq_ignore_ascii_case("auto") {
+        return Ok(MineThreads::Auto);
+    }
+    s.parse::<u32>()
+        .map(|n| MineThreads::Fixed(n.max(1)))
+        .map_err(|_| format!("expected a thread count or `auto`, got `{}`", s))
+}
+
+/// Longest poll interval accepted, in seconds.
+const MAX_POLL_INTERVAL: u64 = 86_400;
+
+/// A poll interval in seconds, as a whole number of ticks.
+fn parse_poll_interval(s: &str) -> Result<u64, String> {
+    let secs: f64 = s.parse().map_err(|_| format!("expected seconds, got `{}`", s))?;
+    if secs > MAX_POLL_INTERVAL as f64 {
+        return Err(format!("must be at most {} seconds (one day)", MAX_POLL_INTERVAL));
+    }
+    let ticks = (secs * 1000.0 / app::TICK_MS as f64).round();
+    if !ticks.is_finite() || ticks < 1.0 {
+        return Err(format!("must be at least one tick ({}ms)", app::TICK_MS));
+    }
+    Ok(ticks as u64)
+}
+
+fn parse_explorer_url(s: &str) -> Result<String, String> {
+    if !config::ExplorerConfig::is_block_template(s) {
+        return Err("must contain {height} or {hash}".to_string());
+    }
+    Ok(s.to_string())
+}
+
+/// Leave one core free for the node and the UI.
+fn auto_mine_threads() -> u32 {
+    std::thread::available_parallelism()
+        .map_or(1, |n| n.get() as u32)
+        .saturating_sub(1)
+        .max(1)
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum StartView {
+    Dashboard,
+    Grid,
+    Peers,
+    History,
+}
+
+/// Where the API cookie may live relative to the blocknet dir, in priority
+/// order. `data/api.cookie` is the canonical location; the rest cover
+/// nonstandard deployments.
+const COOKIE_RELATIVE_PATHS: &[&[&str]] = &[
+    &["data", "api.cookie"],
+    &["api.cookie"],
+    &[".cookie"],
+    &["cookie"],
+];
+
+fn cookie_paths(dir: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+    COOKIE_RELATIVE_PATHS
+        .iter()
+        .map(move |parts| parts.iter().fold(dir.to_path_buf(), |p, part| p.join(part)))
 }
 
 /// Check if a directory looks like a blocknet data directory.
 fn has_cookie(dir: &Path) -> bool {
-    dir.join("data").join("api.cookie").is_file()
+    cookie_paths(dir).any(|p| p.is_file())
 }
 
 /// Try to find the blocknet data directory automatically.
@@ -61,32 +298,32 @@ fn discover_blocknet_dir() -> Option<PathBuf> {
     }
 
     // macOS: ~/Library/Application Support/Blocknet
-    if cfg!(target_os = "macos") {
-        if let Ok(home) = std::env::var("HOME") {
-            let mac_dir = PathBuf::from(home).join("Library/Application Support/Blocknet");
-            if has_cookie(&mac_dir) {
-                return Some(mac_dir);
-            }
+    if cfg!(target_os = "macos")
+        && let Ok(home) = std::env::var("HOME")
+    {
+        let mac_dir = PathBuf::from(home).join("Library/Application Support/Blocknet");
+        if has_cookie(&mac_dir) {
+            return Some(mac_dir);
         }
     }
 
     // Linux: ~/.blocknet
-    if cfg!(target_os = "linux") {
-        if let Ok(home) = std::env::var("HOME") {
-            let linux_dir = PathBuf::from(home).join(".blocknet");
-            if has_cookie(&linux_dir) {
-                return Some(linux_dir);
-            }
+    if cfg!(target_os = "linux")
+        && let Ok(home) = std::env::var("HOME")
+    {
+        let linux_dir = PathBuf::from(home).join(".blocknet");
+        if has_cookie(&linux_dir) {
+            return Some(linux_dir);
         }
     }
 
     // Windows: %APPDATA%\Blocknet
-    if cfg!(target_os = "windows") {
-        if let Ok(appdata) = std::env::var("APPDATA") {
-            let win_dir = PathBuf::from(appdata).join("Blocknet");
-            if has_cookie(&win_dir) {
-                return Some(win_dir);
-            }
+    if cfg!(target_os = "windows")
+        && let Ok(appdata) = std::env::var("APPDATA")
+    {
+        let win_dir = PathBuf::from(appdata).join("Blocknet");
+        if has_cookie(&win_dir) {
+            return Some(win_dir);
         }
     }
 
@@ -94,22 +331,22 @@ fn discover_blocknet_dir() -> Option<PathBuf> {
 }
 
 fn default_blocknet_dir() -> Option<PathBuf> {
-    if cfg!(target_os = "macos") {
-        if let Ok(home) = std::env::var("HOME") {
-            return Some(PathBuf::from(home).join("Library/Application Support/Blocknet"));
-        }
+    if cfg!(target_os = "macos")
+        && let Ok(home) = std::env::var("HOME")
+    {
+        return Some(PathBuf::from(home).join("Library/Application Support/Blocknet"));
     }
 
-    if cfg!(target_os = "linux") {
-        if let Ok(home) = std::env::var("HOME") {
-            return Some(PathBuf::from(home).join(".blocknet"));
-        }
+    if cfg!(target_os = "linux")
+        && let Ok(home) = std::env::var("HOME")
+    {
+        return Some(PathBuf::from(home).join(".blocknet"));
     }
 
-    if cfg!(target_os = "windows") {
-        if let Ok(appdata) = std::env::var("APPDATA") {
-            return Some(PathBuf::from(appdata).join("Blocknet"));
-        }
+    if cfg!(target_os = "windows")
+        && let Ok(appdata) = std::env::var("APPDATA")
+    {
+        return Some(PathBuf::from(appdata).join("Blocknet"));
     }
 
     None
@@ -122,6 +359,44 @@ fn is_local_host(host: &str) -> bool {
     )
 }
 
+/// The pieces of `--url`/`BNTUI_API_URL`.
+struct ApiUrl {
+    scheme: String,
+    host: String,
+    port: u16,
+    cookie: Option<String>,
+}
+
+/// Parse `http(s)://host[:port][#cookie=<path>]`. The port defaults to 8332
+/// for http (the daemon's own) and 443 for https (a reverse proxy).
+fn parse_api_url(s: &str) -> Result<ApiUrl, String> {
+    let url = reqwest::Url::parse(s).map_err(|e| format!("invalid API URL '{}': {}", s, e))?;
+    let scheme = url.scheme();
+    if scheme != "http" && scheme != "https" {
+        return Err(format!("API URL '{}' must start with http:// or https://", s));
+    }
+    let host = url
+        .host_str()
+        .filter(|h| !h.is_empty())
+        .ok_or_else(|| format!("API URL '{}' has no host", s))?;
+    if url.path() != "/" || url.query().is_some() {
+        return Err(format!("API URL '{}' can't have a path or query; give only host and port", s));
+    }
+    let cookie = match url.fragment() {
+        None | Some("") => None,
+        Some(frag) => match frag.strip_prefix("cookie=") {
+            Some(path) if !path.is_empty() => Some(path.to_string()),
+            _ => return Err(format!("API URL fragment '#{}' should be #cookie=<path>", frag)),
+        },
+    };
+    Ok(ApiUrl {
+        scheme: scheme.to_string(),
+        host: host.to_string(),
+        port: url.port().unwrap_or(if scheme == "https" { 443 } else { 8332 }),
+        cookie,
+    })
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum BinaryOs {
     Linux,
@@ -138,6 +413,28 @@ enum BinaryArch {
     Unknown,
 }
 
+impl BinaryOs {
+    fn label(self) -> &'static str {
+        match self {
+            BinaryOs::Linux => "linux",
+            BinaryOs::Macos => "macos",
+            BinaryOs::Windows => "windows",
+            BinaryOs::Unknown => "unknown",
+        }
+    }
+}
+
+impl BinaryArch {
+    fn label(self) -> &'static str {
+        match self {
+            BinaryArch::X86_64 => "x86_64",
+            BinaryArch::Aarch64 => "aarch64",
+            BinaryArch::X86 => "x86",
+            BinaryArch::Unknown => "unknown",
+        }
+    }
+}
+
 fn runtime_os() -> BinaryOs {
     #[cfg(target_os = "linux")]
     {
@@ -321,10 +618,59 @@ fn select_embedded_daemon() -> Option<&'static EmbeddedBinary> {
         })
 }
 
-fn write_embedded_binary(entry: &EmbeddedBinary) -> Result<PathBuf, String> {
-    let mut path = std::env::temp_dir().join("bntui-embedded-daemon");
-    std::fs::create_dir_all(&path).map_err(|e| format!("can't create temp dir: {e}"))?;
-    path.push(entry.name);
+fn find_embedded_daemon(name: &str) -> Result<&'static EmbeddedBinary, String> {
+    EMBEDDED_BINARIES
+        .iter()
+        .find(|entry| entry.name == name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = EMBEDDED_BINARIES.iter().map(|e| e.name).collect();
+            format!(
+                "no embedded daemon named '{}' (available: {})",
+                name,
+                names.join(", ")
+            )
+        })
+}
+
+fn list_embedded_daemons() {
+    let selected = select_embedded_daemon().map(|e| e.name);
+    if EMBEDDED_BINARIES.is_empty() {
+        println!("no embedded daemon binaries (binaries/ was empty at build time)");
+        return;
+    }
+    for entry in EMBEDDED_BINARIES {
+        let (os, arch) = detect_binary_target(entry);
+        let marker = if selected == Some(entry.name) { "*" } else { " " };
+        println!(
+            "{} {:<40} {:<8} {:<8} {:>6.1} MB",
+            marker,
+            entry.name,
+            os.label(),
+            arch.label(),
+            entry.bytes.len() as f64 / 1_000_000.0
+        );
+    }
+    println!();
+    println!("* = auto-selected for this platform");
+}
+
+/// Places to unpack the embedded daemon, in order. The temp dir comes first;
+/// on hardened systems it is often mounted `noexec`, so fall back to the
+/// user cache dir and then the blocknet dir itself.
+fn embedded_binary_dirs(blocknet_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![std::env::temp_dir().join("bntui-embedded-daemon")];
+    if let Some(cache) = std::env::var_os("XDG_CACHE_HOME").filter(|v| !v.is_empty()) {
+        dirs.push(PathBuf::from(cache).join("bntui"));
+    } else if let Ok(home) = std::env::var("HOME") {
+        dirs.push(PathBuf::from(home).join(".cache").join("bntui"));
+    }
+    dirs.push(blocknet_dir.join("bin"));
+    dirs
+}
+
+fn write_embedded_binary(entry: &EmbeddedBinary, dir: &Path) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("can't create {}: {e}", dir.display()))?;
+    let path = dir.join(entry.name);
     std::fs::write(&path, entry.bytes).map_err(|e| format!("can't write embedded daemon: {e}"))?;
 
     #[cfg(unix)]
@@ -341,20 +687,75 @@ fn write_embedded_binary(entry: &EmbeddedBinary) -> Result<PathBuf, String> {
     Ok(path)
 }
 
+/// An embedded daemon this bntui process started, with what it takes to
+/// start it again (`R`).
+struct EmbeddedDaemon {
+    child: std::process::Child,
+    host: String,
+    port: u16,
+    blocknet_dir: PathBuf,
+    binary: Option<String>,
+    args: Vec<String>,
+    cookie_path: PathBuf,
+    api_config: api::ApiConfig,
+    // bntui.lock, held while this daemon is ours
+    _lock: Option<lock::DaemonLock>,
+}
+
+/// What autostarting the embedded daemon came to.
+enum Autostart {
+    Started {
+        path: PathBuf,
+        child: std::process::Child,
+        lock: Option<lock::DaemonLock>,
+    },
+    /// another bntui already manages a daemon for this Blocknet directory
+    Joined { pid: u32, port: u16 },
+}
+
+/// Start the embedded daemon, unless another bntui has already started one
+/// for `blocknet_dir` (see `lock`).
+fn autostart_embedded_daemon(
+    host: &str,
+    port: u16,
+    blocknet_dir: &Path,
+    binary: Option<&str>,
+    extra_args: &[String],
+) -> Result<Autostart, String> {
+    let lock = match lock::acquire(blocknet_dir, port) {
+        Ok(lock::Acquire::Acquired(lock)) => Some(lock),
+        Ok(lock::Acquire::HeldBy { pid, port }) => return Ok(Autostart::Joined { pid, port }),
+        // the lock is a courtesy; don't refuse to start over it
+        Err(e) => {
+            crate::log::write(&format!("daemon lock: {}", e));
+            None
+        }
+    };
+    let (path, child) = try_spawn_embedded_daemon(host, port, blocknet_dir, binary, extra_args)?;
+    Ok(Autostart::Started { path, child, lock })
+}
+
+/// Arguments bntui passes to the embedded daemon itself; `--daemon-arg`
+/// values that repeat one are warned about.
+const MANAGED_DAEMON_ARGS: &[&str] = &["--api", "--daemon", "--data", "--wallet"];
+
 fn try_spawn_embedded_daemon(
     host: &str,
     port: u16,
     blocknet_dir: &Path,
-) -> Result<PathBuf, String> {
+    binary: Option<&str>,
+    extra_args: &[String],
+) -> Result<(PathBuf, std::process::Child), String> {
     if std::env::var("BNTUI_SKIP_EMBEDDED_DAEMON").ok().as_deref() == Some("1") {
         return Err("embedded daemon autostart disabled (BNTUI_SKIP_EMBEDDED_DAEMON=1)".to_string());
     }
 
-    let entry = select_embedded_daemon().ok_or_else(|| {
-        "no embedded daemon binary found for this platform in binaries/".to_string()
-    })?;
-    let daemon_path = write_embedded_binary(entry)?;
-
+    let entry = match binary {
+        Some(name) => find_embedded_daemon(name)?,
+        None => select_embedded_daemon().ok_or_else(|| {
+            "no embedded daemon binary found for this platform in binaries/".to_string()
+        })?,
+    };
     let api_addr = format!("{}:{}", host, port);
     let data_dir = blocknet_dir.join("data");
     let wallet_path = blocknet_dir.join("wallet.dat");
@@ -362,29 +763,63 @@ fn try_spawn_embedded_daemon(
     std::fs::create_dir_all(&data_dir)
         .map_err(|e| format!("can't create data dir {}: {}", data_dir.display(), e))?;
 
-    let mut cmd = Command::new(&daemon_path);
-    cmd.arg("--api")
-        .arg(&api_addr)
-        .arg("--daemon")
-        .arg("--data")
-        .arg(&data_dir)
-        .arg("--wallet")
-        .arg(&wallet_path);
-    cmd.spawn()
-        .map_err(|e| format!("failed to launch embedded daemon {}: {}", daemon_path.display(), e))?;
+    let mut failures = Vec::new();
+    for dir in embedded_binary_dirs(blocknet_dir) {
+        let daemon_path = match write_embedded_binary(entry, &dir) {
+            Ok(path) => path,
+            Err(e) => {
+                failures.push(e);
+                continue;
+            }
+        };
+
+        let mut cmd = Command::new(&daemon_path);
+        cmd.arg("--api")
+            .arg(&api_addr)
+            .arg("--daemon")
+            .arg("--data")
+            .arg(&data_dir)
+            .arg("--wallet")
+            .arg(&wallet_path)
+            .args(extra_args);
+        match cmd.spawn() {
+            Ok(child) => return Ok((daemon_path, child)),
+            // a noexec mount refuses to run the file even though it is 0755
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                let _ = std::fs::remove_file(&daemon_path);
+                failures.push(format!("{}: {} (mounted noexec?)", daemon_path.display(), e));
+            }
+            Err(e) => {
+                return Err(format!(
+                    "failed to launch embedded daemon {}: {}",
+                    daemon_path.display(),
+                    e
+                ));
+            }
+        }
+    }
 
-    Ok(daemon_path)
+    Err(format!(
+        "failed to launch embedded daemon from any location ({}); \
+         if these are mounted noexec, set TMPDIR to an executable directory \
+         or start the daemon yourself",
+        failures.join("; ")
+    ))
 }
 
-async fn wait_for_daemon(base_url: &str, cookie_path: &Path, timeout_secs: u64) -> Result<api::ApiClient, String> {
+async fn wait_for_daemon(
+    base_url: &str,
+    cookie_path: &Path,
+    api_config: &api::ApiConfig,
+    timeout_secs: u64,
+) -> Result<api::ApiClient, String> {
     let start = std::time::Instant::now();
     while start.elapsed().as_secs() < timeout_secs {
-        if cookie_path.is_file() {
-            if let Ok(client) = api::ApiClient::new(base_url, &cookie_path.to_string_lossy()) {
-                if client.get_status().await.is_ok() {
-                    return Ok(client);
-                }
-            }
+        if cookie_path.is_file()
+            && let Ok(client) = api::ApiClient::new(base_url, &cookie_path.to_string_lossy(), api_config)
+            && client.get_status().await.is_ok()
+        {
+            return Ok(client);
         }
         tokio::time::sleep(std::time::Duration::from_millis(250)).await;
     }
@@ -420,34 +855,59 @@ fn discover_cookie_candidates(primary: &Path, blocknet_dir: &Path) -> Vec<PathBu
         candidates.push(cwd.join("data").join("api.cookie"));
     }
 
-    candidates.push(blocknet_dir.join("data").join("api.cookie"));
+    candidates.extend(cookie_paths(blocknet_dir));
 
-    if cfg!(target_os = "macos") {
-        if let Ok(home) = std::env::var("HOME") {
-            candidates.push(
-                PathBuf::from(&home)
-                    .join("Library/Application Support/com.blocknet.wallet/data/api.cookie"),
-            );
-            candidates.push(
-                PathBuf::from(home).join("Library/Application Support/Blocknet/data/api.cookie"),
-            );
-        }
+    if cfg!(target_os = "macos")
+        && let Ok(home) = std::env::var("HOME")
+    {
+        candidates.push(
+            PathBuf::from(&home)
+                .join("Library/Application Support/com.blocknet.wallet/data/api.cookie"),
+        );
+        candidates.push(
+            PathBuf::from(home).join("Library/Application Support/Blocknet/data/api.cookie"),
+        );
     }
 
     candidates.dedup();
     candidates
 }
 
+/// Connection details a running daemon may publish in `data/daemon.json`.
+#[derive(serde::Deserialize)]
+struct DaemonInfo {
+    host: Option<String>,
+    port: Option<u16>,
+    cookie: Option<String>,
+}
+
+fn daemon_info_path(blocknet_dir: &Path) -> PathBuf {
+    blocknet_dir.join("data").join("daemon.json")
+}
+
+fn read_daemon_info(blocknet_dir: &Path) -> Option<DaemonInfo> {
+    let text = std::fs::read_to_string(daemon_info_path(blocknet_dir)).ok()?;
+    match serde_json::from_str(&text) {
+        Ok(info) => Some(info),
+        Err(e) => {
+            eprintln!("warning: ignoring malformed daemon.json: {e}");
+            None
+        }
+    }
+}
+
 async fn try_connect_local_with_cookie(
+    scheme: &str,
     host: &str,
     port: u16,
     cookie_path: &Path,
+    api_config: &api::ApiConfig,
 ) -> Option<api::ApiClient> {
     if !cookie_path.is_file() {
         return None;
     }
-    let base_url = format!("http://{}:{}", host, port);
-    let client = api::ApiClient::new(&base_url, &cookie_path.to_string_lossy()).ok()?;
+    let base_url = format!("{}://{}:{}", scheme, host, port);
+    let client = api::ApiClient::new(&base_url, &cookie_path.to_string_lossy(), api_config).ok()?;
     if client.get_status().await.is_ok() {
         Some(client)
     } else {
@@ -483,354 +943,1704 @@ fn copy_to_clipboard(text: &str) -> Result<(), String> {
         }
     }
     // Last resort: arboard (works on macOS/Windows, unreliable on Wayland)
-    if let Ok(mut cb) = arboard::Clipboard::new() {
-        if cb.set_text(text).is_ok() {
-            return Ok(());
-        }
+    if let Ok(mut cb) = arboard::Clipboard::new()
+        && cb.set_text(text).is_ok()
+    {
+        return Ok(());
     }
     Err("Install wl-clipboard or xclip".to_string())
 }
 
-fn open_in_browser(url: &str) {
+/// Read the clipboard as text, trying the same tools as `copy_to_clipboard`.
+/// An empty clipboard (or one holding something other than text) reads as
+/// an empty string.
+fn paste_from_clipboard() -> Result<String, String> {
     use std::process::{Command, Stdio};
-    #[cfg(target_os = "linux")]
-    {
-        let _ = Command::new("xdg-open")
-            .arg(url)
-            .stdout(Stdio::null())
+    let tools: &[(&str, &[&str])] = &[
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-o"]),
+    ];
+    // a tool that runs but fails has nothing to give
+    let mut found_tool = false;
+    for (cmd, args) in tools {
+        if let Ok(out) = Command::new(cmd)
+            .args(*args)
+            .stdin(Stdio::null())
             .stderr(Stdio::null())
-            .spawn();
+            .output()
+        {
+            if out.status.success() {
+                return Ok(String::from_utf8_lossy(&out.stdout).into_owned());
+            }
+            found_tool = true;
+        }
     }
-    #[cfg(target_os = "macos")]
+    if let Ok(mut cb) = arboard::Clipboard::new()
+        && let Ok(text) = cb.get_text()
     {
-        let _ = Command::new("open")
-            .arg(url)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+        return Ok(text);
     }
+    if found_tool {
+        Ok(String::new())
+    } else {
+        Err("Install wl-clipboard or xclip".to_string())
+    }
+}
+
+/// Hand a URL or filesystem path to the OS default handler (browser, file
+/// manager, ...).
+fn open_path(target: &str) -> Result<(), String> {
+    use std::process::{Command, Stdio};
+    #[cfg(target_os = "linux")]
+    let mut cmd = {
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(target);
+        cmd
+    };
+    #[cfg(target_os = "macos")]
+    let mut cmd = {
+        let mut cmd = Command::new("open");
+        cmd.arg(target);
+        cmd
+    };
     #[cfg(target_os = "windows")]
-    {
-        let _ = Command::new("cmd")
-            .args(["/C", "start", "", url])
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", "", target]);
+        cmd
+    };
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    return Err("opening files is not supported on this platform".to_string());
+
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    cmd.stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Switch to a fresh receive address; the old one stays in the receive dialog.
+async fn rotate_address(api: &api::ApiClient, app: &mut app::App) {
+    match api.new_address().await {
+        Ok(addr) => {
+            app.rotate_address(addr.address);
+            app.event("payment received; rotated receive address");
+            app.set_flash("Payment received; switched to a new address".to_string());
+        }
+        Err(e) => app.event(format!("address rotation failed: {}", e)),
     }
 }
 
-async fn run(
-    terminal: &mut ratatui::DefaultTerminal,
+async fn fetch_address(api: &api::ApiClient, app: &mut app::App) {
+    match api.get_address().await {
+        Ok(addr) => {
+            app.wallet_address = Some(addr.address);
+            app.wallet_missing = false;
+        }
+        Err(e) => {
+            app.wallet_missing = api::is_no_wallet_error(&e);
+        }
+    }
+}
+
+/// Ask for the storage passphrase on the terminal (twice when setting it up)
+/// and unlock the vault with it.
+fn unlock_storage() -> Result<crypt::Key, String> {
+    let read = |prompt: &str| rpassword::prompt_password(prompt).map_err(|e| e.to_string());
+    if crypt::vault_exists() {
+        return crypt::unlock(&read("Passphrase for ~/.bntui: ")?);
+    }
+    let pass = read("New passphrase for encrypting ~/.bntui: ")?;
+    if pass.is_empty() {
+        return Err("empty passphrase".to_string());
+    }
+    if read("Repeat passphrase: ")? != pass {
+        return Err("passphrases don't match".to_string());
+    }
+    crypt::unlock(&pass)
+}
+
+async fn fetch_peers(api: &api::ApiClient, app: &mut app::App) {
+    if !app.peers_supported {
+        return;
+    }
+    match api.get_peers().await {
+        Ok(peers) => {
+            app.peers = Some(peers);
+            app.peers_error = None;
+        }
+        Err(e) if api::is_not_found(&e) => {
+            app.peers_supported = false;
+            app.peers_error = Some("this daemon doesn't list peers".to_string());
+        }
+        Err(e) => app.peers_error = Some(e),
+    }
+}
+
+/// Refresh the suggested fee for the send dialog. Failures leave the last
+/// estimate (or none) in place; a daemon without the endpoint isn't asked
+/// again.
+async fn fetch_fee_estimate(api: &api::ApiClient, app: &mut app::App) {
+    if !app.fee_estimate_supported {
+        return;
+    }
+    match api.get_fee_estimate().await {
+        Ok(estimate) => app.fee_estimate = Some(estimate),
+        Err(e) if api::is_not_found(&e) => app.fee_estimate_supported = false,
+        Err(_) => {}
+    }
+}
+
+/// Send `send`; on success close the dialog, record and flash the txid. On
+/// failure the send is handed back with the error so it can be retried.
+async fn send_pending(
     api: &api::ApiClient,
-) -> color_eyre::Result<()> {
-    let mut app = app::App::new();
+    app: &mut app::App,
+    send: app::PendingSend,
+) -> Result<(), (String, app::PendingSend)> {
+    let txid = match api.send_to_with_fee(&send.address, send.amount, send.fee).await {
+        Ok(txid) => txid,
+        Err(e) => return Err((e, send)),
+    };
+    app.input_mode = app::InputMode::Normal;
+    let fee_note = send.fee.map_or(String::new(), |f| format!(" (fee {})", types::format_bnt(f)));
+    app.wallet_event(format!(
+        "sent {} to {}{}: {}",
+        types::format_bnt(send.amount),
+        send.address,
+        fee_note,
+        txid
+    ));
+    app.log_tx(&txid, &send.address, send.amount);
+    let hint = if app.address_book.label_for(&send.address).is_none() {
+        " · [S] save address"
+    } else {
+        ""
+    };
+    app.set_flash_persistent(format!("Sent! tx: {}{}", txid, hint), txid);
+    app.last_send_address = Some(send.address);
+    Ok(())
+}
 
-    // initial data load
-    if let Ok(stats) = api.get_status().await {
-        app.status = Some(stats);
+async fn fetch_mempool_txs(api: &api::ApiClient, app: &mut app::App) {
+    if !app.mempool_txs_supported {
+        return;
+    }
+    match api.get_mempool_txs().await {
+        Ok(txs) => {
+            app.mempool_txs = Some(txs);
+            app.mempool_txs_error = None;
+        }
+        Err(e) if api::is_not_found(&e) => {
+            app.mempool_txs_supported = false;
+            app.show_mempool_txs = false;
+            app.set_flash("This daemon doesn't list mempool transactions".to_string());
+        }
+        Err(e) => app.mempool_txs_error = Some(e),
+    }
+}
+
+// how far back to look for the fork point before giving up
+const MAX_REORG_SCAN: usize = 100;
+
+/// Detect a reorg by checking our newest block against the node's chain, and
+/// drop the replaced blocks so the normal catch-up refetches them. Returns
+/// how many blocks were dropped.
+async fn unwind_reorg(
+    api: &api::ApiClient,
+    app: &mut app::App,
+    node_height: u64,
+    best_hash: &str,
+) -> usize {
+    let Some(tip) = app.chain_blocks.last() else {
+        return 0;
+    };
+    let tip_ok = if node_height == tip.height {
+        tip.hash == best_hash
+    } else if node_height > tip.height {
+        // only costs a request when new blocks have arrived
+        match api.get_block(tip.height).await {
+            Ok(theirs) => theirs.hash == tip.hash,
+            Err(_) => true,
+        }
+    } else {
+        false
+    };
+    if tip_ok {
+        return 0;
+    }
+
+    let mut dropped = 0;
+    while let Some(ours) = app.chain_blocks.last() {
+        if dropped >= MAX_REORG_SCAN {
+            break;
+        }
+        if ours.height <= node_height {
+            match api.get_block(ours.height).await {
+                Ok(theirs) if theirs.hash == ours.hash => break,
+                Ok(_) => {}
+                // can't tell from here; keep what we have
+                Err(_) => break,
+            }
+        }
+        app.chain_blocks.pop();
+        app.block_cubes.pop();
+        dropped += 1;
     }
+    app.selected = app.selected.min(app.chain_blocks.len().saturating_sub(1));
+    dropped
+}
+
+// consecutive 404s during backfill before assuming the node is pruned
+const PRUNE_MISSES: u32 = 3;
+
+/// Load up to `app.backfill_depth` blocks ending at `tip`, replacing
+/// whatever is loaded.
+async fn backfill(api: &api::ApiClient, app: &mut app::App, tip: u64) {
+    app.chain_blocks.clear();
+    app.block_cubes.clear();
+    app.prune_height = None;
+    app.selected = 0;
+    app.grid_scroll_offset = 0;
+    app.backfill = Some(app::Backfill {
+        next: tip,
+        floor: tip.saturating_sub(app.backfill_depth.saturating_sub(1)),
+        misses: 0,
+    });
+    // the newest batch now; run() fetches the rest a batch per tick
+    backfill_batch(api, app).await;
+}
 
-    if let Some(ref stats) = app.status {
-        let start = stats.chain_height.saturating_sub(999);
-        for h in start..=stats.chain_height {
-            if let Ok(block) = api.get_block(h).await {
-                app.chain_blocks.push(block);
+// block requests in flight at once while backfilling
+const BACKFILL_BATCH: u64 = 16;
+
+/// Fetch the next `BACKFILL_BATCH` blocks below the loaded ones, in
+/// parallel, and put them in front of the chain.
+async fn backfill_batch(api: &api::ApiClient, app: &mut app::App) {
+    let Some(mut fill) = app.backfill.take() else {
+        return;
+    };
+    let low = fill.next.saturating_sub(BACKFILL_BATCH - 1).max(fill.floor);
+    // walk down from the tip so a pruned node is noticed after a few
+    // misses instead of 404ing through the whole range
+    let results: Vec<_> = stream::iter((low..=fill.next).rev())
+        .map(|h| api.get_block(h))
+        .buffered(BACKFILL_BATCH as usize)
+        .collect()
+        .await;
+    let mut fetched = Vec::new();
+    let mut pruned = false;
+    // highest height the node didn't have
+    let mut first_miss = None;
+    for (height, result) in (low..=fill.next).rev().zip(results) {
+        match result {
+            Ok(block) => {
+                fill.misses = 0;
+                fetched.push(block);
+            }
+            Err(e) if api::is_not_found(&e) => {
+                first_miss.get_or_insert(height);
+                fill.misses += 1;
+                if fill.misses >= PRUNE_MISSES {
+                    pruned = true;
+                    break;
+                }
             }
+            Err(_) => fill.misses = 0,
+        }
+    }
+
+    fetched.reverse();
+    let n = fetched.len();
+    let was_empty = app.chain_blocks.is_empty();
+    app.chain_blocks.splice(0..0, fetched);
+    app.block_cubes.splice(0..0, (0..n).map(|_| cube::SpinCube::new()));
+    // keep the selection on the same block
+    app.selected = if was_empty {
+        app.chain_blocks.len().saturating_sub(1)
+    } else {
+        app.selected + n
+    };
+
+    if pruned {
+        // with nothing loaded (even the tip 404ed) the limit is where the
+        // misses started
+        app.prune_height = app.chain_blocks.first().map(|b| b.height).or(first_miss);
+        if let Some(h) = app.prune_height {
+            app.event(format!("node is pruned; no blocks below #{}", h));
         }
-        app.block_cubes = app
-            .chain_blocks
-            .iter()
-            .map(|_| cube::SpinCube::new())
-            .collect();
-        app.selected = app.chain_blocks.len().saturating_sub(1);
+    } else if low > fill.floor {
+        fill.next = low - 1;
+        app.backfill = Some(fill);
+    }
+    if app.backfill.is_none() {
+        app.event(format!("loaded {} blocks", app.chain_blocks.len()));
+    }
+    app.restore_selection();
+}
+
+async fn load_initial(api: &api::ApiClient, app: &mut app::App) {
+    if let Ok(stats) = api.get_status().await {
+        app.record_sync(&stats);
+        app.status = Some(stats);
+        app.last_status_tick = Some(app.tick_count);
+    }
+
+    // once per session: older daemons have no /api/version, some put it in
+    // the status instead
+    let version = match api.get_version().await {
+        Ok(version) => Some(version),
+        Err(_) => app.status.as_ref().and_then(|s| s.version.clone()),
+    };
+    app.set_daemon_version(version);
+    if let Some(ref version) = app.daemon_version {
+        app.event(format!("daemon version {}", version));
+    }
+
+    if let Some(tip) = app.status.as_ref().map(|s| s.chain_height) {
+        backfill(api, app, tip).await;
     }
 
     if let Ok(mempool) = api.get_mempool().await {
         app.mempool = Some(mempool);
+        app.last_mempool_tick = Some(app.tick_count);
     }
     if let Ok(balance) = api.get_balance().await {
         app.balance = Some(balance);
+        app.last_balance_tick = Some(app.tick_count);
     }
     if let Ok(mining) = api.get_mining().await {
-        app.mining = Some(mining);
+        app.set_mining(mining);
     }
-    if let Ok(addr) = api.get_address().await {
-        app.wallet_address = Some(addr.address);
+    fetch_address(api, app).await;
+}
+
+/// How long a stopping daemon gets to flush its database after SIGTERM
+/// before it is killed outright.
+const DAEMON_STOP_GRACE_SECS: u64 = 10;
+
+/// Ask `child` to exit and wait for it, falling back to a hard kill if it
+/// is still running after `DAEMON_STOP_GRACE_SECS`. Windows has no SIGTERM,
+/// so there it is killed straight away.
+async fn stop_daemon_child(
+    terminal: &mut ratatui::DefaultTerminal,
+    child: &mut std::process::Child,
+    app: &mut app::App,
+) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        let sent = Command::new("kill")
+            .args(["-TERM", &child.id().to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success());
+        if sent {
+            for tenths in 0..DAEMON_STOP_GRACE_SECS * 10 {
+                if !matches!(child.try_wait(), Ok(None)) {
+                    return Ok(());
+                }
+                if tenths % 10 == 0 {
+                    app.set_flash(format!("Stopping embedded daemon… {}s", tenths / 10));
+                    terminal
+                        .draw(|frame| ui::render(frame, app))
+                        .map_err(|e| e.to_string())?;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+            app.event("embedded daemon ignored SIGTERM; killing it");
+        }
     }
+    let _ = child.kill();
+    let _ = child.wait();
+    Ok(())
+}
 
-    let mut should_quit = false;
-    loop {
-        terminal.draw(|frame| ui::render(frame, &mut app))?;
+/// Stop the embedded daemon, start it again, and wait for it to answer,
+/// redrawing with a progress flash while it comes up.
+async fn restart_embedded_daemon(
+    terminal: &mut ratatui::DefaultTerminal,
+    daemon: &mut EmbeddedDaemon,
+    app: &mut app::App,
+) -> Result<api::ApiClient, String> {
+    stop_daemon_child(terminal, &mut daemon.child, app).await?;
+    let (_, child) = try_spawn_embedded_daemon(
+        &daemon.host,
+        daemon.port,
+        &daemon.blocknet_dir,
+        daemon.binary.as_deref(),
+        &daemon.args,
+    )?;
+    daemon.child = child;
+
+    let base_url = format!("http://{}:{}", daemon.host, daemon.port);
+    for secs in 0..30 {
+        app.set_flash(format!("Restarting embedded daemon… {}s", secs));
+        terminal
+            .draw(|frame| ui::render(frame, app))
+            .map_err(|e| e.to_string())?;
+        if let Ok(api) = wait_for_daemon(&base_url, &daemon.cookie_path, &daemon.api_config, 1).await {
+            return Ok(api);
+        }
+    }
+    Err("daemon did not become ready within 30s".to_string())
+}
+
+/// Whether the loop step from tick `prev` to `now` passed a multiple of
+/// `interval`; a step covers several ticks while animations are paused.
+fn tick_due(prev: u64, now: u64, interval: u64) -> bool {
+    prev / interval != now / interval
+}
+
+async fn run(
+    terminal: &mut ratatui::DefaultTerminal,
+    api: &api::ApiClient,
+    price_feed: Option<&api::PriceFeed>,
+    exposer: Option<&expose::Exposer>,
+    mut embedded: Option<EmbeddedDaemon>,
+    mut app: app::App,
+) -> color_eyre::Result<()> {
+    if !app.replay {
+        load_initial(api, &mut app).await;
+    }
+
+    // client rebuilt for a restarted embedded daemon or by a reconnect,
+    // replacing `api`
+    let mut restarted: Option<api::ApiClient> = None;
+    let mut restart_requested = false;
+    // poll everything now instead of waiting for the next tick
+    let mut refresh_now = false;
+    let mut should_quit = false;
+    loop {
+        if restart_requested {
+            restart_requested = false;
+            if let Some(ref mut daemon) = embedded {
+                let before = app.checkpoint();
+                match restart_embedded_daemon(terminal, daemon, &mut app).await {
+                    Ok(client) => {
+                        restarted = Some(client);
+                        app.event("embedded daemon restarted");
+                        app.set_flash("Embedded daemon restarted".to_string());
+                        app.checkpoint = Some(("Daemon restarted", before));
+                        refresh_now = true;
+                    }
+                    Err(e) => {
+                        app.event(format!("daemon restart failed: {}", e));
+                        app.set_flash_persistent(format!("Daemon restart failed: {}", e), e);
+                    }
+                }
+            }
+        }
+        // a daemon that stopped answering may have restarted with a new
+        // cookie, or left dead pooled connections: start afresh, on a backoff
+        let reconnect_poll = !app.replay && app.reconnecting() && app.tick_count >= app.reconnect_at;
+        if reconnect_poll {
+            match restarted.as_ref().unwrap_or(api).reconnect() {
+                Ok(client) => restarted = Some(client),
+                Err(e) => crate::log::write(&format!("reconnect: {}", e)),
+            }
+        }
+        let api = restarted.as_ref().unwrap_or(api);
+        if !app.replay && app.backfill.is_some() {
+            backfill_batch(api, &mut app).await;
+        }
+
+        terminal.draw(|frame| ui::render(frame, &mut app))?;
 
         // input handling
         while crossterm::event::poll(std::time::Duration::from_millis(0))? {
             let event = crossterm::event::read()?;
-            if let Event::Key(key) = event {
-                if key.kind == KeyEventKind::Press {
-                    match app.input_mode {
-                        app::InputMode::Normal => match key.code {
-                            KeyCode::Esc => {
+            if let Event::Key(key) = event
+                && key.kind == KeyEventKind::Press
+            {
+                // any key ends a new-block hold
+                app.release_held_block();
+                match app.input_mode {
+                    app::InputMode::Normal => match key.code {
+                        KeyCode::Char('s' | 'B' | 'm' | '+' | '=' | '-' | 'U' | 'l') if app.readonly => {
+                            app.set_flash("Read-only mode".to_string());
+                        }
+                        KeyCode::Esc => {
+                            if app.flash_message.is_some() {
                                 app.flash_message = None;
+                            } else if app.reorg_banner.is_some() {
+                                app.reorg_banner = None;
+                            } else {
+                                app.show_events = false;
+                                app.show_peer_summary = false;
+                                app.show_mempool_txs = false;
                             }
-                            KeyCode::Char('c') => {
-                                let copyable = app.flash_message.as_ref()
-                                    .and_then(|f| f.copyable.clone());
-                                if let Some(text) = copyable {
-                                    match copy_to_clipboard(&text) {
-                                        Ok(_) => {
-                                            app.set_flash("Copied!".to_string());
-                                        }
-                                        Err(e) => {
-                                            app.set_flash(format!("Clipboard error: {}", e));
-                                        }
+                        }
+                        KeyCode::Char('L') => app.show_events = !app.show_events,
+                        KeyCode::Char('n') => {
+                            app.show_peer_summary = !app.show_peer_summary;
+                            if app.show_peer_summary && !app.replay {
+                                fetch_peers(api, &mut app).await;
+                            }
+                        }
+                        KeyCode::Char('c') => {
+                            let copyable = app.flash_message.as_ref()
+                                .and_then(|f| f.copyable.clone());
+                            if let Some(text) = copyable {
+                                match copy_to_clipboard(&text) {
+                                    Ok(_) => {
+                                        app.set_flash("Copied!".to_string());
                                     }
+                                    Err(e) => {
+                                        app.set_flash(format!("Clipboard error: {}", e));
+                                    }
+                                }
+                            } else if app.shows_history()
+                                && let Some(txid) = app.selected_tx_log_txid().map(str::to_string)
+                            {
+                                match copy_to_clipboard(&txid) {
+                                    Ok(_) => app.set_flash("Copied txid".to_string()),
+                                    Err(e) => app.set_flash(format!("Clipboard error: {}", e)),
                                 }
                             }
-                            KeyCode::Char('q') => should_quit = true,
-                            KeyCode::Char('1') => app.current_view = 1,
-                            KeyCode::Char('2') => app.current_view = 2,
-                            KeyCode::Char('s') => {
-                                app.input_mode = app::InputMode::SendDialog {
-                                    address: String::new(),
-                                    amount: String::new(),
-                                    focused: 0,
-                                    error: None,
-                                };
+                        }
+                        KeyCode::Char('q') => should_quit = true,
+                        KeyCode::F(5) if !app.replay => {
+                            // during an outage the reconnect checkpoint stays
+                            if !app.poll_failing {
+                                app.checkpoint = Some(("Refreshed", app.checkpoint()));
                             }
-                            KeyCode::Char('m') => {
-                                if let Some(ref mining) = app.mining {
-                                    if mining.running {
-                                        api.stop_mining().await.ok();
-                                    } else {
-                                        api.start_mining().await.ok();
+                            // and retry a reconnect right away
+                            app.reconnect_at = app.tick_count;
+                            refresh_now = true;
+                        }
+                        KeyCode::Char('R') => {
+                            if embedded.is_some() {
+                                restart_requested = true;
+                            } else {
+                                app.set_flash(
+                                    "Daemon wasn't started by bntui; restart it yourself".to_string(),
+                                );
+                            }
+                        }
+                        KeyCode::Char('D') => match app.write_snapshot() {
+                            Ok(path) => {
+                                app.set_flash(format!("Snapshot saved: {}", path.display()))
+                            }
+                            Err(e) => app.set_flash(format!("Snapshot failed: {}", e)),
+                        },
+                        KeyCode::Char('P') => {
+                            // a failed size query shouldn't end the session
+                            let (width, height) =
+                                terminal.size().map_or(app.screen_size, |s| (s.width, s.height));
+                            let text = ui::screen_text(&mut app, width, height);
+                            match copy_to_clipboard(&text) {
+                                Ok(()) => app.set_flash("Screen copied to clipboard".into()),
+                                Err(_) => match app::write_screen(&text) {
+                                    Ok(path) => {
+                                        app.set_flash(format!("Screen saved: {}", path.display()))
+                                    }
+                                    Err(e) => app.set_flash(format!("Screen copy failed: {}", e)),
+                                },
+                            }
+                        }
+                        KeyCode::Char('e') if app.shows_dashboard() => {
+                            match app.write_difficulty_csv() {
+                                Ok(path) => app.set_flash(format!("Difficulty saved: {}", path.display())),
+                                Err(e) => app.set_flash(format!("Export failed: {}", e)),
+                            }
+                        }
+                        KeyCode::Char('E') if app.shows_grid() => match app.write_blocks_export() {
+                            Ok(path) => app.set_flash(format!("Blocks saved: {}", path.display())),
+                            Err(e) => app.set_flash(format!("Export failed: {}", e)),
+                        },
+                        KeyCode::Char('1') => app.current_view = 1,
+                        KeyCode::Char('2') => app.current_view = 2,
+                        KeyCode::Char('4') => {
+                            app.current_view = 4;
+                            app.load_tx_log();
+                        }
+                        KeyCode::Char('3') => {
+                            app.current_view = 3;
+                            if !app.replay {
+                                fetch_peers(api, &mut app).await;
+                            }
+                        }
+                        KeyCode::Char('|')
+                            if !app.split_view && app.screen_size.0 < ui::SPLIT_MIN_WIDTH =>
+                        {
+                            app.set_flash(format!(
+                                "Terminal too narrow for split view (need {} columns)",
+                                ui::SPLIT_MIN_WIDTH
+                            ));
+                        }
+                        KeyCode::Char('|') => app.split_view = !app.split_view,
+                        KeyCode::Char('s') => {
+                            app.input_mode = app::InputMode::SendDialog {
+                                address: String::new(),
+                                amount: String::new(),
+                                fee: String::new(),
+                                focused: 0,
+                                error: None,
+                                confirm: None,
+                                saved: None,
+                            };
+                            if !app.replay {
+                                fetch_fee_estimate(api, &mut app).await;
+                            }
+                        }
+                        KeyCode::Char('S') => match app.last_send_address.clone() {
+                            None => app.set_flash("Nothing sent yet this session".to_string()),
+                            Some(address) => match app.address_book.label_for(&address) {
+                                Some(label) => app.set_flash(format!("Already saved as \"{}\"", label)),
+                                None => {
+                                    app.input_mode = app::InputMode::SaveAddress {
+                                        address,
+                                        label: String::new(),
+                                        error: None,
+                                    };
+                                }
+                            },
+                        },
+                        KeyCode::Char('U') if !app.replay => {
+                            app.input_mode = app::InputMode::UnlockWallet {
+                                passphrase: String::new(),
+                                error: None,
+                                send: None,
+                            };
+                        }
+                        KeyCode::Char('l') if !app.replay => {
+                            app.input_mode = app::InputMode::LockWallet { error: None };
+                        }
+                        KeyCode::Char('x') if app.shows_dashboard() => {
+                            if !app.mempool_txs_supported {
+                                app.set_flash("This daemon doesn't list mempool transactions".to_string());
+                            } else {
+                                app.show_mempool_txs = !app.show_mempool_txs;
+                                if app.show_mempool_txs && !app.replay {
+                                    fetch_mempool_txs(api, &mut app).await;
+                                }
+                            }
+                        }
+                        KeyCode::Char('X') if app.shows_dashboard() && app.show_mempool_txs => {
+                            app.mempool_txs_ascending = !app.mempool_txs_ascending;
+                        }
+                        KeyCode::Char('/') if app.shows_dashboard() && app.show_mempool_txs => {
+                            app.input_mode = app::InputMode::MempoolFilter {
+                                previous: app.mempool_txs_filter.clone(),
+                            };
+                        }
+                        KeyCode::Char('/') => {
+                            app.input_mode = app::InputMode::FindTx {
+                                query: String::new(),
+                                error: None,
+                            };
+                        }
+                        KeyCode::Char('B') => {
+                            app.input_mode = app::InputMode::Broadcast {
+                                hex: String::new(),
+                                confirm: false,
+                                error: None,
+                            };
+                        }
+                        KeyCode::Char('m') => {
+                            if let Some(ref mining) = app.mining {
+                                if mining.running {
+                                    api.stop_mining().await.ok();
+                                    app.event("mining stopped");
+                                } else {
+                                    api.start_mining().await.ok();
+                                    app.event("mining started");
+                                }
+                                if let Ok(m) = api.get_mining().await {
+                                    app.set_mining(m);
+                                }
+                            }
+                        }
+                        KeyCode::Char('+') | KeyCode::Char('=') => {
+                            if let Some(ref mining) = app.mining {
+                                let new_threads = mining.threads + 1;
+                                let was_running = mining.running;
+
+                                api.set_threads(new_threads).await.ok();
+                                app.event(format!("mining threads set to {}", new_threads));
+                                if let Ok(m) = api.get_mining().await {
+                                    app.set_mining(m);
+                                }
+                                if was_running {
+                                    app.threads_pending_restart = Some(app.tick_count);
+                                }
+                            }
+                        }
+                        KeyCode::Char('-') => {
+                            if let Some(ref mining) = app.mining
+                                && mining.threads > 1
+                            {
+                                let new_threads = mining.threads - 1;
+                                let was_running = mining.running;
+
+                                api.set_threads(new_threads).await.ok();
+                                app.event(format!("mining threads set to {}", new_threads));
+                                if let Ok(m) = api.get_mining().await {
+                                    app.set_mining(m);
+                                }
+                                if was_running {
+                                    app.threads_pending_restart = Some(app.tick_count);
+                                }
+                            }
+                        }
+                        KeyCode::Char('j') if app.shows_peers() => {
+                            // stop once the last peer is on screen, as the view draws it
+                            let len = app.peers.as_ref().map_or(0, |p| p.len());
+                            let last = len.saturating_sub(app.peers_rows);
+                            app.peers_scroll = (app.peers_scroll + 1).min(last);
+                        }
+                        KeyCode::Char('k') if app.shows_peers() => {
+                            app.peers_scroll = app.peers_scroll.saturating_sub(1);
+                        }
+                        KeyCode::Char('j')
+                            if app.shows_history()
+                                && app.tx_log_selected + 1
+                                    < app.tx_log_entries.as_ref().map_or(0, |e| e.len()) =>
+                        {
+                            app.tx_log_selected += 1;
+                        }
+                        KeyCode::Char('k') if app.shows_history() => {
+                            app.tx_log_selected = app.tx_log_selected.saturating_sub(1);
+                        }
+                        KeyCode::Char('j')
+                            if app.shows_dashboard()
+                                && !app.shows_grid()
+                                && app.dashboard_scroll < app.dashboard_max_scroll =>
+                        {
+                            app.dashboard_scroll += 1;
+                        }
+                        KeyCode::Char('k')
+                            if app.shows_dashboard() && !app.shows_grid() && app.dashboard_scroll > 0 =>
+                        {
+                            app.dashboard_scroll -= 1;
+                        }
+                        KeyCode::Char('j')
+                            if app.shows_grid()
+                                && !app.block_cubes.is_empty()
+                                && app.selected + 1 < app.block_cubes.len() =>
+                        {
+                            app.selected += 1;
+                        }
+                        KeyCode::Char('k') if app.shows_grid() && app.selected > 0 => {
+                            app.selected -= 1;
+                        }
+                        KeyCode::Char('J') if app.shows_grid() && !app.block_cubes.is_empty() => {
+                            let jump = app.blocks_per_row;
+                            let max = app.block_cubes.len() - 1;
+                            app.selected = (app.selected + jump).min(max);
+                        }
+                        KeyCode::Char('K') if app.shows_grid() && app.selected > 0 => {
+                            let jump = app.blocks_per_row;
+                            app.selected = app.selected.saturating_sub(jump);
+                        }
+                        KeyCode::Char('f') if app.shows_grid() => {
+                            app.follow_tip = !app.follow_tip;
+                        }
+                        KeyCode::Char('u') if app.shows_grid() => {
+                            let selected = app.selected;
+                            if let Some(height) = app.chain_blocks.get(selected).map(|b| b.height) {
+                                match api.get_block(height).await {
+                                    Ok(block) => {
+                                        app.chain_blocks[selected] = block;
+                                        app.set_flash(format!("Refreshed block #{}", height));
                                     }
-                                    if let Ok(m) = api.get_mining().await {
-                                        app.mining = Some(m);
+                                    Err(e) => {
+                                        app.set_flash(format!("Refresh failed: {}", e));
                                     }
                                 }
                             }
-                            KeyCode::Char('+') | KeyCode::Char('=') => {
-                                if let Some(ref mining) = app.mining {
-                                    let new_threads = mining.threads + 1;
-                                    let was_running = mining.running;
-
-                                    api.set_threads(new_threads).await.ok();
-                                    if let Ok(m) = api.get_mining().await {
-                                        app.mining = Some(m);
+                        }
+                        KeyCode::Char('d') if app.shows_grid() => {
+                            app.info_density = app.info_density.next();
+                            app.set_flash(format!("Block info: {}", app.info_density.label()));
+                        }
+                        KeyCode::Char('r') => {
+                            if let Some(ref addr) = app.wallet_address {
+                                let addr = addr.clone();
+                                match copy_to_clipboard(&addr) {
+                                    Ok(_) => {
+                                        app.set_flash(format!("Address copied: {}", addr))
                                     }
-                                    if was_running {
-                                        app.threads_pending_restart = Some(app.tick_count);
+                                    Err(e) => {
+                                        app.set_flash(format!("Clipboard error: {}", e))
                                     }
                                 }
+                                app.input_mode = app::InputMode::Receive { selected: 0 };
+                            } else if app.wallet_missing && app.readonly {
+                                app.set_flash("No wallet on this node (read-only mode)".to_string());
+                            } else if app.wallet_missing {
+                                app.input_mode = app::InputMode::CreateWallet { error: None };
+                            } else {
+                                fetch_address(api, &mut app).await;
+                                if app.wallet_address.is_none() {
+                                    app.set_flash("Wallet address not available yet".to_string());
+                                }
                             }
-                            KeyCode::Char('-') => {
-                                if let Some(ref mining) = app.mining {
-                                    if mining.threads > 1 {
-                                        let new_threads = mining.threads - 1;
-                                        let was_running = mining.running;
-
-                                        api.set_threads(new_threads).await.ok();
-                                        if let Ok(m) = api.get_mining().await {
-                                            app.mining = Some(m);
-                                        }
-                                        if was_running {
-                                            app.threads_pending_restart = Some(app.tick_count);
-                                        }
+                        }
+                        KeyCode::Char('y') | KeyCode::Char('Y') => {
+                            // the highlighted history row, else the last txid
+                            // sent this session; or its explorer URL
+                            let txid = if app.shows_history() {
+                                app.selected_tx_log_txid()
+                            } else {
+                                app.tx_history.iter().rev().map(|e| e.txid.as_str()).find(|t| *t != "sent")
+                            };
+                            match txid.map(str::to_string) {
+                                Some(txid) => {
+                                    let (text, what) = if key.code == KeyCode::Char('Y') {
+                                        (app.config.explorer.tx(&txid), "Explorer URL")
+                                    } else {
+                                        (txid, "Txid")
+                                    };
+                                    match copy_to_clipboard(&text) {
+                                        Ok(()) => app.set_flash(format!("{} copied: {}", what, text)),
+                                        Err(e) => app.set_flash(format!("Clipboard error: {}", e)),
                                     }
                                 }
+                                None if app.shows_history() => app.set_flash("No transaction selected".to_string()),
+                                None => app.set_flash("No transactions sent this session".to_string()),
+                            }
+                        }
+                        KeyCode::Enter if app.shows_history() => {
+                            if let Some(txid) = app.selected_tx_log_txid() {
+                                let url = app.config.explorer.tx(txid);
+                                match open_path(&url) {
+                                    Ok(()) => app.set_flash("Opening transaction in browser…".to_string()),
+                                    Err(e) => app.set_flash(format!("Could not open browser: {}", e)),
+                                }
+                            }
+                        }
+                        KeyCode::Char('i') => {
+                            let peer_id = app.status.as_ref().map(|s| s.peer_id.clone());
+                            match peer_id {
+                                Some(id) if !id.is_empty() => match copy_to_clipboard(&id) {
+                                    Ok(()) => app.set_flash(format!("Node ID copied: {}", id)),
+                                    Err(e) => app.set_flash(format!("Clipboard error: {}", e)),
+                                },
+                                _ => app.set_flash("Node ID: — (no status yet)".to_string()),
                             }
-                            KeyCode::Char('j') => {
-                                if app.current_view == 2
-                                    && !app.block_cubes.is_empty()
-                                    && app.selected + 1 < app.block_cubes.len()
-                                {
-                                    app.selected += 1;
+                        }
+                        KeyCode::Char('$') if app.fiat_rate.is_some() => {
+                            app.show_fiat = !app.show_fiat;
+                            app.set_flash(format!("Fiat value: {}", if app.show_fiat { "on" } else { "off" }));
+                        }
+                        KeyCode::Char('p') if app.shows_dashboard() => {
+                            app.plasma_style = app.plasma_style.next();
+                            app.set_flash(format!("Plasma: {}", app.plasma_style.label()));
+                        }
+                        KeyCode::Char('a') => {
+                            let pending = app.pending_txids().join("\n");
+                            if pending.is_empty() {
+                                app.set_flash("No pending sends".to_string());
+                            } else {
+                                let n = pending.lines().count();
+                                match copy_to_clipboard(&pending) {
+                                    Ok(()) => app.set_flash(format!("Copied {} txids", n)),
+                                    Err(e) => app.set_flash(format!("Clipboard error: {}", e)),
                                 }
                             }
-                            KeyCode::Char('k') => {
-                                if app.current_view == 2 && app.selected > 0 {
-                                    app.selected -= 1;
+                        }
+                        KeyCode::Char('M') if app.shows_grid() => {
+                            app.show_only_mine = !app.show_only_mine;
+                            if app.show_only_mine {
+                                let msg = match app.mined_in_view() {
+                                    0 => "None of your blocks are loaded (only blocks found while bntui runs are known)".to_string(),
+                                    n => format!("{} of your blocks in view", n),
+                                };
+                                app.set_flash(msg);
+                            }
+                        }
+                        KeyCode::Char('h') if app.shows_grid() => {
+                            let hash = app.chain_blocks.get(app.selected).map(|b| (b.height, b.hash.clone()));
+                            if let Some((height, hash)) = hash {
+                                match copy_to_clipboard(&hash) {
+                                    Ok(_) => app.set_flash(format!("Copied hash of block #{}", height)),
+                                    Err(e) => app.set_flash(format!("Clipboard error: {}", e)),
                                 }
                             }
-                            KeyCode::Char('J') => {
-                                if app.current_view == 2 && !app.block_cubes.is_empty() {
-                                    let jump = app.blocks_per_row;
-                                    let max = app.block_cubes.len() - 1;
-                                    app.selected = (app.selected + jump).min(max);
+                        }
+                        KeyCode::Char('g') if app.shows_grid() => {
+                            app.input_mode = app::InputMode::GotoBlock {
+                                input: String::new(),
+                                error: None,
+                            };
+                        }
+                        KeyCode::Char('C') if app.shows_dashboard() => {
+                            app.chart_metric = app.chart_metric.next();
+                            app.set_flash(format!("Chain chart: {}", app.chart_metric.label()));
+                        }
+                        KeyCode::Char(' ') => {
+                            app.animations_paused = !app.animations_paused;
+                            app.set_flash(format!(
+                                "Animations: {}",
+                                if app.animations_paused { "paused" } else { "on" }
+                            ));
+                        }
+                        KeyCode::Char('z') if app.shows_grid() => {
+                            app.left_panel_mode = app.left_panel_mode.next();
+                            app.set_flash(format!("Left panel: {}", app.left_panel_mode.label()));
+                        }
+                        // on the grid, b toggles bar cells
+                        KeyCode::Char('b') if app.shows_dashboard() && !app.shows_grid() => {
+                            if app.balance_mark.take().is_some() {
+                                app.set_flash("Mark cleared".to_string());
+                            } else if let Some(ref balance) = app.balance {
+                                let height = app.status.as_ref().map_or(0, |s| s.chain_height);
+                                app.balance_mark = Some((balance.total, height));
+                                app.set_flash(format!(
+                                    "Marked {} at #{}",
+                                    app.fmt_bnt(balance.total),
+                                    height
+                                ));
+                            } else {
+                                app.set_flash("No balance to mark yet".to_string());
+                            }
+                        }
+                        KeyCode::Char('b') if app.shows_grid() => {
+                            app.grid_cell_style = match app.grid_cell_style {
+                                app::GridCellStyle::Flat => app::GridCellStyle::Bars,
+                                app::GridCellStyle::Bars => app::GridCellStyle::Flat,
+                            };
+                        }
+                        KeyCode::Char('t') if app.shows_grid() => {
+                            app.grid_timestamps = !app.grid_timestamps;
+                        }
+                        KeyCode::Down if app.shows_grid() => app.move_tx_cursor(true),
+                        KeyCode::Up if app.shows_grid() => app.move_tx_cursor(false),
+                        KeyCode::PageDown if app.shows_grid() => app.scroll_txs(true),
+                        KeyCode::PageUp if app.shows_grid() => app.scroll_txs(false),
+                        KeyCode::Enter if app.shows_grid() => {
+                            let picked = app
+                                .highlighted_tx()
+                                .map(|(b, i)| (b.height, i, b.transactions[i].hash.clone()));
+                            match picked {
+                                Some((block_height, tx_index, hash)) => {
+                                    let detail = if app.replay {
+                                        Err("no node to ask in replay".to_string())
+                                    } else {
+                                        api.get_tx_detail(&hash).await
+                                    };
+                                    app.input_mode = app::InputMode::TxDetail {
+                                        block_height,
+                                        tx_index,
+                                        detail,
+                                        scroll: 0,
+                                    };
                                 }
+                                None => app.set_flash("Pick a transaction with ↑/↓ first".to_string()),
                             }
-                            KeyCode::Char('K') => {
-                                if app.current_view == 2 && app.selected > 0 {
-                                    let jump = app.blocks_per_row;
-                                    app.selected = app.selected.saturating_sub(jump);
+                        }
+                        KeyCode::Char('o') => match app.blocknet_dir.clone() {
+                            Some(dir) => match open_path(&dir.to_string_lossy()) {
+                                Ok(()) => app.set_flash(format!("Opened {}", dir.display())),
+                                Err(e) => app.set_flash(format!("Could not open {}: {}", dir.display(), e)),
+                            },
+                            None => app.set_flash("No data directory".to_string()),
+                        },
+                        KeyCode::Char('v') => {
+                            if app.shows_grid()
+                                && let Some(block) = app.chain_blocks.get(app.selected)
+                            {
+                                let url = app.config.explorer.block(block.height, &block.hash);
+                                match open_path(&url) {
+                                    Ok(()) => app.set_flash("Opening block in browser…".to_string()),
+                                    Err(e) => app.set_flash(format!("Could not open browser: {}", e)),
                                 }
                             }
-                            KeyCode::Char('r') => {
-                                if let Some(ref addr) = app.wallet_address {
-                                    let addr = addr.clone();
-                                    match copy_to_clipboard(&addr) {
-                                        Ok(_) => {
-                                            app.set_flash(format!("Address copied: {}", addr))
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::SendDialog {
+                        ref mut address,
+                        ref mut amount,
+                        ref mut fee,
+                        ref mut focused,
+                        ref mut error,
+                        ref mut confirm,
+                        ref mut saved,
+                    } => match key.code {
+                        // back out of the large-send confirmation first
+                        KeyCode::Esc if confirm.is_some() => {
+                            *confirm = None;
+                            *error = None;
+                        }
+                        KeyCode::Esc => {
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Tab | KeyCode::Down | KeyCode::Up | KeyCode::BackTab
+                            if confirm.is_some() => {}
+                        // the address book picker is only a stop when there's
+                        // something in it
+                        KeyCode::Tab | KeyCode::Down => {
+                            let stops = if app.address_book.is_empty() { 3 } else { 4 };
+                            *focused = (*focused + 1) % stops;
+                        }
+                        KeyCode::BackTab | KeyCode::Up => {
+                            let stops = if app.address_book.is_empty() { 3 } else { 4 };
+                            *focused = (*focused + stops - 1) % stops;
+                        }
+                        KeyCode::Left | KeyCode::Right if *focused == 3 && confirm.is_none() => {
+                            let n = app.address_book.entries.len();
+                            if n > 0 {
+                                let i = match (*saved, key.code) {
+                                    (None, KeyCode::Left) => n - 1,
+                                    (None, _) => 0,
+                                    (Some(i), KeyCode::Left) => (i + n - 1) % n,
+                                    (Some(i), _) => (i + 1) % n,
+                                };
+                                *saved = Some(i);
+                                *address = app.address_book.entries[i].1.clone();
+                                *error = None;
+                            }
+                        }
+                        KeyCode::Backspace => {
+                            let field = match confirm {
+                                Some(typed) => Some(typed),
+                                None if *focused == 0 => Some(address),
+                                None if *focused == 1 => Some(amount),
+                                None if *focused == 2 => Some(fee),
+                                None => None,
+                            };
+                            if let Some(field) = field {
+                                field.pop();
+                                *error = None;
+                            }
+                        }
+                        KeyCode::Enter => {
+                            let addr = address.clone();
+                            let amt_str = amount.clone();
+
+                            if addr.is_empty() {
+                                *error = Some("Address is required".to_string());
+                            } else if !types::is_valid_address(&addr) {
+                                *error = Some("Invalid address format".to_string());
+                            } else if amt_str.is_empty() {
+                                *error = Some("Amount is required".to_string());
+                            } else if !fee.is_empty() && types::parse_bnt_amount(fee).is_none() {
+                                *error = Some("Invalid fee format".to_string());
+                            } else {
+                                match types::parse_bnt_amount(&amt_str) {
+                                    None => {
+                                        *error =
+                                            Some("Invalid amount format".to_string());
+                                    }
+                                    Some(0) => {
+                                        *error =
+                                            Some("Amount must be greater than 0".to_string());
+                                    }
+                                    // the dialog already shows the live
+                                    // "exceeds spendable" warning
+                                    Some(atomic)
+                                        if app
+                                            .balance
+                                            .as_ref()
+                                            .is_some_and(|b| atomic > b.spendable) => {}
+                                    Some(atomic)
+                                        if app
+                                            .config
+                                            .wallet
+                                            .max_send_atomic()
+                                            .is_some_and(|limit| atomic > limit)
+                                            && confirm.as_deref() != Some("CONFIRM") =>
+                                    {
+                                        if confirm.is_some() {
+                                            *error = Some("Type CONFIRM to send".to_string());
+                                        } else {
+                                            *confirm = Some(String::new());
                                         }
-                                        Err(e) => {
-                                            app.set_flash(format!("Clipboard error: {}", e))
+                                    }
+                                    Some(atomic) => {
+                                        let send = app::PendingSend {
+                                            address: addr,
+                                            amount: atomic,
+                                            fee: types::parse_bnt_amount(fee),
+                                        };
+                                        match send_pending(api, &mut app, send).await {
+                                            Ok(()) => {}
+                                            // ask for the passphrase, then send again
+                                            Err((e, send)) if api::is_locked_error(&e) => {
+                                                app.event("send: wallet is locked");
+                                                app.input_mode = app::InputMode::UnlockWallet {
+                                                    passphrase: String::new(),
+                                                    error: None,
+                                                    send: Some(send),
+                                                };
+                                            }
+                                            Err((e, _)) => {
+                                                if let app::InputMode::SendDialog { ref mut error, .. } =
+                                                    app.input_mode
+                                                {
+                                                    *error = Some(e.clone());
+                                                }
+                                                app.event(format!("send failed: {}", e));
+                                            }
                                         }
                                     }
                                 }
                             }
-                            KeyCode::Char('v') => {
-                                if app.current_view == 2 {
-                                    if let Some(block) = app.chain_blocks.get(app.selected) {
-                                        let url = format!(
-                                            "https://explorer.blocknetcrypto.com/block/{}",
-                                            block.height
-                                        );
-                                        open_in_browser(&url);
-                                        app.set_flash("Opening block in browser…".to_string());
+                        }
+                        // into the focused field, never the CONFIRM prompt
+                        KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            let field = match *focused {
+                                _ if confirm.is_some() => None,
+                                0 => Some(address),
+                                1 => Some(amount),
+                                2 => Some(fee),
+                                _ => None,
+                            };
+                            if let Some(field) = field {
+                                match paste_from_clipboard() {
+                                    Ok(text) => {
+                                        let text: String = text.split_whitespace().collect();
+                                        if !text.is_empty() {
+                                            field.push_str(&text);
+                                            *error = None;
+                                        }
                                     }
+                                    Err(e) => *error = Some(e),
                                 }
                             }
-                            _ => {}
-                        },
-                        app::InputMode::SendDialog {
-                            ref mut address,
-                            ref mut amount,
-                            ref mut focused,
-                            ref mut error,
-                        } => match key.code {
-                            KeyCode::Esc => {
-                                app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Char(c) => {
+                            let field = match confirm {
+                                Some(typed) => Some(typed),
+                                None if *focused == 0 => Some(address),
+                                None if *focused == 1 => Some(amount),
+                                None if *focused == 2 => Some(fee),
+                                None => None,
+                            };
+                            if let Some(field) = field {
+                                field.push(c);
+                                *error = None;
                             }
-                            KeyCode::Tab | KeyCode::Down | KeyCode::Up => {
-                                *focused = if *focused == 0 { 1 } else { 0 };
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::TxDetail {
+                        block_height,
+                        tx_index,
+                        ref mut scroll,
+                        ..
+                    } => match key.code {
+                        KeyCode::Esc => app.input_mode = app::InputMode::Normal,
+                        KeyCode::Char('j') | KeyCode::Down => *scroll = scroll.saturating_add(1),
+                        KeyCode::Char('k') | KeyCode::Up => *scroll = scroll.saturating_sub(1),
+                        KeyCode::Char('c') => {
+                            let hash = app
+                                .chain_blocks
+                                .iter()
+                                .find(|b| b.height == block_height)
+                                .and_then(|b| b.transactions.get(tx_index))
+                                .map(|tx| tx.hash.clone());
+                            if let Some(hash) = hash {
+                                match copy_to_clipboard(&hash) {
+                                    Ok(_) => app.set_flash("Copied tx hash".to_string()),
+                                    Err(e) => app.set_flash(format!("Clipboard error: {}", e)),
+                                }
                             }
-                            KeyCode::BackTab => {
-                                *focused = if *focused == 0 { 1 } else { 0 };
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::GotoBlock {
+                        ref mut input,
+                        ref mut error,
+                    } => match key.code {
+                        KeyCode::Esc => {
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Backspace => {
+                            input.pop();
+                            *error = None;
+                        }
+                        KeyCode::Enter => {
+                            let result = match input.trim().trim_start_matches('#').parse::<u64>() {
+                                Ok(height) => app.goto_block(height),
+                                Err(_) => Err("Not a block height".to_string()),
+                            };
+                            match result {
+                                Ok(msg) => {
+                                    app.input_mode = app::InputMode::Normal;
+                                    app.set_flash(msg);
+                                }
+                                Err(e) => {
+                                    if let app::InputMode::GotoBlock { ref mut error, .. } =
+                                        app.input_mode
+                                    {
+                                        *error = Some(e);
+                                    }
+                                }
                             }
-                            KeyCode::Backspace => {
-                                let field =
-                                    if *focused == 0 { address } else { amount };
-                                field.pop();
-                                *error = None;
+                        }
+                        KeyCode::Char(c) => {
+                            input.push(c);
+                            *error = None;
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::FindTx {
+                        ref mut query,
+                        ref mut error,
+                    } => match key.code {
+                        KeyCode::Esc => {
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Backspace => {
+                            query.pop();
+                            *error = None;
+                        }
+                        KeyCode::Enter => {
+                            let txid = query.trim().to_ascii_lowercase();
+                            let found = match app.find_tx_block(&txid) {
+                                Some(idx) => Ok(idx),
+                                None if !types::is_valid_txid(&txid) => {
+                                    Err("A txid is 64 hex characters".to_string())
+                                }
+                                None => match api.get_tx_height(&txid).await {
+                                    Ok(Some(height)) => app
+                                        .chain_blocks
+                                        .iter()
+                                        .position(|b| b.height == height)
+                                        .ok_or(format!(
+                                            "In block #{}, outside the loaded range",
+                                            height
+                                        )),
+                                    Ok(None) => Err("Not mined yet".to_string()),
+                                    Err(_) => Err("Not found in loaded range".to_string()),
+                                },
+                            };
+                            match found {
+                                Ok(idx) => {
+                                    let height = app.chain_blocks[idx].height;
+                                    app.input_mode = app::InputMode::Normal;
+                                    app.selected = idx;
+                                    app.follow_tip = false;
+                                    app.highlight_txid = Some(txid);
+                                    if !app.shows_grid() {
+                                        app.current_view = 2;
+                                    }
+                                    app.set_flash(format!("Found in block #{}", height));
+                                }
+                                Err(e) => {
+                                    if let app::InputMode::FindTx { ref mut error, .. } =
+                                        app.input_mode
+                                    {
+                                        *error = Some(e);
+                                    }
+                                }
                             }
-                            KeyCode::Enter => {
-                                let addr = address.clone();
-                                let amt_str = amount.clone();
-
-                                if addr.is_empty() {
-                                    *error = Some("Address is required".to_string());
-                                } else if amt_str.is_empty() {
-                                    *error = Some("Amount is required".to_string());
-                                } else {
-                                    match types::parse_bnt_amount(&amt_str) {
-                                        None => {
-                                            *error =
-                                                Some("Invalid amount format".to_string());
-                                        }
-                                        Some(0) => {
-                                            *error =
-                                                Some("Amount must be greater than 0".to_string());
-                                        }
-                                        Some(atomic) => {
-                                            match api.send_to(&addr, atomic).await {
-                                                Ok(txid) => {
-                                                    app.input_mode =
-                                                        app::InputMode::Normal;
-                                                    app.log_tx(&txid, &addr, atomic);
-                                                    app.set_flash_persistent(
-                                                        format!("Sent! tx: {}", txid),
-                                                        txid,
-                                                    );
-                                                }
-                                                Err(e) => {
-                                                    *error = Some(e);
-                                                }
+                        }
+                        KeyCode::Char(c) if !c.is_whitespace() => {
+                            query.push(c);
+                            *error = None;
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::SaveAddress {
+                        ref address,
+                        ref mut label,
+                        ref mut error,
+                    } => match key.code {
+                        KeyCode::Esc => app.input_mode = app::InputMode::Normal,
+                        KeyCode::Backspace => {
+                            label.pop();
+                            *error = None;
+                        }
+                        KeyCode::Enter => match app.address_book.add(label, address, app.cipher.as_ref()) {
+                            Ok(()) => {
+                                let msg = format!("Saved {} as \"{}\"", address, label.trim());
+                                app.wallet_event(msg.clone());
+                                app.set_flash(msg);
+                                app.input_mode = app::InputMode::Normal;
+                            }
+                            Err(e) => *error = Some(e),
+                        },
+                        KeyCode::Char(c) => {
+                            label.push(c);
+                            *error = None;
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::UnlockWallet {
+                        ref mut passphrase,
+                        ref mut error,
+                        ref mut send,
+                    } => match key.code {
+                        KeyCode::Esc => {
+                            if send.is_some() {
+                                app.set_flash("Send cancelled: the wallet is locked".to_string());
+                            }
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Backspace => {
+                            passphrase.pop();
+                            *error = None;
+                        }
+                        KeyCode::Enter if passphrase.is_empty() => {
+                            *error = Some("Passphrase is required".to_string());
+                        }
+                        KeyCode::Enter => {
+                            let passphrase = std::mem::take(passphrase);
+                            let send = send.take();
+                            match api.unlock_wallet(&passphrase, app::UNLOCK_SECS).await {
+                                Err(e) => {
+                                    app.event(format!("unlock failed: {}", e));
+                                    app.input_mode = app::InputMode::UnlockWallet {
+                                        passphrase: String::new(),
+                                        error: Some(e),
+                                        send,
+                                    };
+                                }
+                                Ok(()) => {
+                                    app.event("wallet unlocked");
+                                    app.input_mode = app::InputMode::Normal;
+                                    match send {
+                                        Some(send) => {
+                                            if let Err((e, _)) = send_pending(api, &mut app, send).await {
+                                                app.event(format!("send failed: {}", e));
+                                                app.set_flash(format!("Send failed: {}", e));
                                             }
                                         }
+                                        None => app.set_flash(format!(
+                                            "Wallet unlocked for {} minutes",
+                                            app::UNLOCK_SECS / 60
+                                        )),
                                     }
                                 }
                             }
-                            KeyCode::Char(c) => {
-                                let field =
-                                    if *focused == 0 { address } else { amount };
-                                field.push(c);
+                        }
+                        KeyCode::Char(c) => {
+                            passphrase.push(c);
+                            *error = None;
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::MempoolFilter { ref mut previous } => match key.code {
+                        KeyCode::Esc => {
+                            app.mempool_txs_filter = std::mem::take(previous);
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Enter => app.input_mode = app::InputMode::Normal,
+                        KeyCode::Backspace => {
+                            app.mempool_txs_filter.pop();
+                        }
+                        KeyCode::Char(c) if c.is_ascii_hexdigit() => {
+                            app.mempool_txs_filter.push(c.to_ascii_lowercase());
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::Broadcast {
+                        ref mut hex,
+                        ref mut confirm,
+                        ref mut error,
+                    } => match key.code {
+                        KeyCode::Esc => {
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Backspace => {
+                            hex.pop();
+                            *confirm = false;
+                            *error = None;
+                        }
+                        KeyCode::Enter if !*confirm => {
+                            let trimmed = hex.trim().to_string();
+                            if api::is_plausible_tx_hex(&trimmed) {
+                                *hex = trimmed;
+                                *confirm = true;
                                 *error = None;
+                            } else {
+                                *error = Some("Not a hex-encoded transaction".to_string());
+                            }
+                        }
+                        KeyCode::Enter => match api.send_raw(hex).await {
+                            Ok(txid) => {
+                                app.input_mode = app::InputMode::Normal;
+                                app.wallet_event(format!("broadcast raw tx: {}", txid));
+                                app.set_flash_persistent(format!("Broadcast! tx: {}", txid), txid);
+                            }
+                            Err(e) => {
+                                *confirm = false;
+                                *error = Some(e.clone());
+                                app.event(format!("broadcast failed: {}", e));
                             }
-                            _ => {}
                         },
-                    }
+                        KeyCode::Char(c) if !c.is_whitespace() => {
+                            hex.push(c);
+                            *confirm = false;
+                            *error = None;
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::Receive { ref mut selected } => match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Char('j') | KeyCode::Down if *selected < app.previous_addresses.len() => {
+                            *selected += 1;
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => *selected = selected.saturating_sub(1),
+                        KeyCode::Enter | KeyCode::Char('y') => {
+                            let row = *selected;
+                            if let Some(addr) = app.receive_address(row).map(str::to_string) {
+                                match copy_to_clipboard(&addr) {
+                                    Ok(()) => app.set_flash(format!("Address copied: {}", addr)),
+                                    Err(e) => app.set_flash(format!("Clipboard error: {}", e)),
+                                }
+                            }
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        _ => {}
+                    },
+                    app::InputMode::LockWallet { ref mut error } => match key.code {
+                        KeyCode::Esc | KeyCode::Char('n') => {
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Char('y') | KeyCode::Enter => match api.lock_wallet().await {
+                            Ok(()) => {
+                                app.input_mode = app::InputMode::Normal;
+                                app.event("wallet locked");
+                                app.set_flash("Wallet locked".to_string());
+                            }
+                            Err(e) => {
+                                *error = Some(format!("Lock failed: {}", e));
+                            }
+                        },
+                        _ => {}
+                    },
+                    app::InputMode::CreateWallet { ref mut error } => match key.code {
+                        KeyCode::Esc | KeyCode::Char('n') => {
+                            app.input_mode = app::InputMode::Normal;
+                        }
+                        KeyCode::Char('y') | KeyCode::Enter => match api.create_wallet().await {
+                            Ok(()) => {
+                                app.input_mode = app::InputMode::Normal;
+                                app.event("wallet created");
+                                fetch_address(api, &mut app).await;
+                                match app.wallet_address {
+                                    Some(ref addr) => {
+                                        let msg = format!("Wallet created: {}", addr);
+                                        app.set_flash(msg);
+                                    }
+                                    None => app.set_flash(
+                                        "Wallet created; waiting for address…".to_string(),
+                                    ),
+                                }
+                            }
+                            Err(e) => {
+                                *error = Some(e);
+                            }
+                        },
+                        _ => {}
+                    },
                 }
             }
         }
         if should_quit {
+            if !app.replay {
+                app.save_state();
+            }
             break;
         }
 
-        std::thread::sleep(std::time::Duration::from_millis(33));
-        app.tick_count += 1;
+        let step = if app.animations_paused { app::PAUSED_TICK_STEP } else { 1 };
+        std::thread::sleep(std::time::Duration::from_millis(app::TICK_MS * step));
+        let prev_tick = app.tick_count;
+        app.tick_count += step;
 
-        // update animations (only for visible view)
-        if app.current_view == 2 && !app.block_cubes.is_empty() {
-            let speed = app.spin_speed();
-            app.update_selected_cube(speed);
-        }
-        if app.current_view == 1 {
-            app.update_plasma();
-        }
-        app.update_block_found();
+        app.animate();
 
         app.update_flash();
+        app.update_held_block();
 
-        if let Some(changed_tick) = app.threads_pending_restart {
-            if app.tick_count - changed_tick > 15 {
-                app.threads_pending_restart = None;
-                api.stop_mining().await.ok();
-                api.start_mining().await.ok();
-                if let Ok(m) = api.get_mining().await {
-                    app.mining = Some(m);
-                }
+        if let Some(changed_tick) = app.threads_pending_restart
+            && app.tick_count - changed_tick > 15
+        {
+            app.threads_pending_restart = None;
+            api.stop_mining().await.ok();
+            api.start_mining().await.ok();
+            if let Ok(m) = api.get_mining().await {
+                app.set_mining(m);
             }
         }
 
-        // poll status every ~1 second (30 ticks × 33ms)
-        if app.tick_count % 30 == 0 {
-            if let Ok(stats) = api.get_status().await {
-                let new_height = stats.chain_height;
-                let have_height = app.chain_blocks.last().map_or(0, |b| b.height);
-                app.status = Some(stats);
-
-                if new_height > app.prev_chain_height && app.prev_chain_height > 0 {
-                    app.block_found_display = 3.0;
+        // poll status every --status-interval (~1 second by default)
+        // while the daemon is unreachable every request can take the full
+        // timeout, so only check on it at the slower data poll interval
+        let status_interval = if app.poll_failing {
+            app.data_poll_ticks.max(app.status_poll_ticks)
+        } else {
+            app.status_poll_ticks
+        };
+        let status_due = if app.reconnecting() {
+            reconnect_poll
+        } else {
+            refresh_now || tick_due(prev_tick, app.tick_count, status_interval)
+        };
+        let status_poll = if !app.replay && status_due {
+            Some(api.get_status().await)
+        } else {
+            None
+        };
+        if let Some(ref result) = status_poll {
+            app.poll_result(app::PollSource::Status, result.as_ref().err().map(String::as_str));
+        }
+        if let Some(Err(_)) = status_poll {
+            app.status_failed();
+            if app.status_failures == app::RECONNECT_AFTER {
+                app.event("daemon not answering, reconnecting");
+            }
+        }
+        if let Some(Err(_)) = status_poll
+            && !app.poll_failing
+        {
+            app.poll_failing = true;
+            if app.checkpoint.is_none() {
+                app.checkpoint = Some(("Reconnected", app.checkpoint()));
+            }
+        }
+        if let Some(Ok(stats)) = status_poll {
+            app.status_failures = 0;
+            if app.poll_failing {
+                app.poll_failing = false;
+                app.event("connection restored");
+                refresh_now = true;
+            }
+            let new_height = stats.chain_height;
+            let loaded_height = app.chain_blocks.last().map_or(0, |b| b.height);
+            // a node that was wiped and is resyncing drops further than any
+            // reorg, and a different chain has no fork point in range; both
+            // would leave stale blocks behind, so start over from its tip.
+            // Fewer loaded blocks than the scan depth can all be unwound
+            // without finding one; that's a reset too, as catch-up needs a
+            // loaded block to build on
+            let dropped = if new_height + (MAX_REORG_SCAN as u64) < loaded_height {
+                MAX_REORG_SCAN
+            } else {
+                unwind_reorg(api, &mut app, new_height, &stats.best_hash).await
+            };
+            if dropped >= MAX_REORG_SCAN || (dropped > 0 && app.chain_blocks.is_empty()) {
+                let msg = format!(
+                    "Chain reset detected (node at #{}, had #{}), reloading",
+                    new_height, loaded_height
+                );
+                app.event(msg.clone());
+                app.set_flash(msg);
+                backfill(api, &mut app, new_height).await;
+            } else if dropped > 0 {
+                let fork = app.chain_blocks.last().map_or(0, |b| b.height);
+                let msg = format!("Reorg: {} block(s) replaced above #{}", dropped, fork);
+                app.event(msg.clone());
+                if dropped as u64 >= app.config.reorg.alert_depth {
+                    app.reorg_banner = Some(msg);
+                } else {
+                    app.set_flash(msg);
                 }
-                app.prev_chain_height = new_height;
+            }
+            let have_height = app.chain_blocks.last().map_or(0, |b| b.height);
+            app.record_sync(&stats);
+            app.status = Some(stats);
+            app.last_status_tick = Some(app.tick_count);
+            if let Some(exposer) = exposer {
+                exposer.update(&app);
+            }
 
-                // fetch new blocks
-                if new_height > have_height && have_height > 0 {
-                    let was_at_newest = app.selected + 1 >= app.chain_blocks.len();
-                    for h in (have_height + 1)..=new_height {
-                        if let Ok(block) = api.get_block(h).await {
-                            app.chain_blocks.push(block);
-                            app.block_cubes.push(cube::SpinCube::new());
-                        }
-                    }
-                    if was_at_newest && !app.chain_blocks.is_empty() {
-                        app.selected = app.chain_blocks.len() - 1;
+            if new_height > app.prev_chain_height && app.prev_chain_height > 0 {
+                app.block_found_display = 3.0;
+            }
+            app.prev_chain_height = new_height;
+
+            // fetch new blocks
+            if new_height > have_height && have_height > 0 {
+                let was_at_newest = app.selected + 1 >= app.chain_blocks.len();
+                for h in (have_height + 1)..=new_height {
+                    if let Ok(block) = api.get_block(h).await {
+                        app.event(format!("new block #{} ({} txs)", h, block.tx_count));
+                        app.chain_blocks.push(block);
+                        app.block_cubes.push(cube::SpinCube::new());
                     }
                 }
+                app.trim_blocks();
+                // a held block keeps the selection until the hold ends
+                if app.follow_tip
+                    && was_at_newest
+                    && app.held_block.is_none()
+                    && !app.chain_blocks.is_empty()
+                {
+                    app.selected = app.chain_blocks.len() - 1;
+                }
+                if let Some(newest) = app.chain_blocks.last().map(|b| b.height)
+                    && newest > have_height
+                {
+                    app.hold_new_block(newest);
+                }
             }
         }
 
         // poll other data every ~3 seconds (90 ticks × 33ms)
-        if app.tick_count % 90 == 0 {
-            if let Ok(mempool) = api.get_mempool().await {
+        // the data poll waits until the status poll gets through again
+        if !app.replay
+            && !app.poll_failing
+            && (refresh_now || tick_due(prev_tick, app.tick_count, app.data_poll_ticks))
+        {
+            let mempool = api.get_mempool().await;
+            app.poll_result(app::PollSource::Mempool, mempool.as_ref().err().map(String::as_str));
+            if let Ok(mempool) = mempool {
+                let was_congested = app.mempool_congestion() == app::Congestion::Congested;
                 app.record_mempool(&mempool);
                 app.mempool = Some(mempool);
+                app.last_mempool_tick = Some(app.tick_count);
+                if !was_congested
+                    && app.config.mempool.alert_flash
+                    && app.mempool_congestion() == app::Congestion::Congested
+                    && let Some(ref mp) = app.mempool
+                {
+                    let msg = format!("Mempool congested: {} txs, {} bytes", mp.count, mp.size_bytes);
+                    app.event(msg.clone());
+                    app.set_flash(msg);
+                }
             }
-            if let Ok(balance) = api.get_balance().await {
+            let balance = api.get_balance().await;
+            // a node without a wallet has its own message in the Wallet panel
+            let balance_error = balance.as_ref().err().filter(|e| !api::is_no_wallet_error(e));
+            app.poll_result(app::PollSource::Balance, balance_error.map(String::as_str));
+            if let Ok(balance) = balance {
+                let received = app.balance.as_ref().is_some_and(|b| balance.total > b.total);
                 app.balance = Some(balance);
+                app.last_balance_tick = Some(app.tick_count);
+                if received && app.config.wallet.auto_rotate_address && !app.readonly {
+                    rotate_address(api, &mut app).await;
+                }
+            }
+            let mining = api.get_mining().await;
+            app.poll_result(app::PollSource::Mining, mining.as_ref().err().map(String::as_str));
+            if let Ok(mining) = mining {
+                app.set_mining(mining);
+                app.attribute_mined();
+                if let Some(exposer) = exposer {
+                    exposer.update(&app);
+                }
+            }
+            // fresh nodes may create their wallet on their own; keep asking
+            if app.wallet_address.is_none() {
+                fetch_address(api, &mut app).await;
+            }
+            if app.show_peer_summary || app.shows_peers() {
+                fetch_peers(api, &mut app).await;
+            }
+            if app.show_mempool_txs && app.shows_dashboard() {
+                fetch_mempool_txs(api, &mut app).await;
             }
-            if let Ok(mining) = api.get_mining().await {
-                app.mining = Some(mining);
+            if let Some(rate) = price_feed.and_then(api::PriceFeed::latest) {
+                app.fiat_rate = Some(rate);
+            }
+            if std::mem::take(&mut refresh_now)
+                && !app.poll_failing
+                && let Some((label, before)) = app.checkpoint.take()
+            {
+                // may name a balance change
+                let msg = format!("{}: {}", label, app.changes_since(&before));
+                app.wallet_event(msg.clone());
+                app.set_flash(msg);
             }
         }
     }
@@ -841,7 +2651,82 @@ async fn run(
 async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let explicit = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+    if cli.list_daemons {
+        list_embedded_daemons();
+        return Ok(());
+    }
+    if let Some(ref name) = cli.daemon_binary
+        && let Err(e) = find_embedded_daemon(name)
+    {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+    for arg in &cli.daemon_args {
+        let flag = arg.split('=').next().unwrap_or(arg);
+        if MANAGED_DAEMON_ARGS.contains(&flag) {
+            eprintln!(
+                "warning: --daemon-arg {} repeats {}, which bntui already passes to the daemon",
+                arg, flag
+            );
+        }
+    }
+
+    if let Some(ref path) = cli.replay {
+        let snapshot = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str::<app::Snapshot>(&text).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                eprintln!("error: can't load snapshot {}: {}", path.display(), e);
+                std::process::exit(1);
+            });
+        let mut app = app::App::new();
+        app.restore(snapshot);
+        app.replay = true;
+        // replays run on the default config, but draw in the configured colors
+        app.theme = theme::Theme::from_config(&config::Config::load().theme);
+        app.accessible = cli.accessible;
+        app.cube_style = cli.cube_style;
+        app.export_format = cli.export_format;
+        app.target_block_time_secs = cli.target_block_time;
+        if let Some(url) = cli.explorer_url.clone() {
+            app.config.explorer.block_url = url;
+        }
+        if let Some(n) = cli.history_len {
+            app.history_len = n as usize;
+        }
+
+        if let Some(ref dir) = cli.frames_to_file {
+            std::fs::create_dir_all(dir)?;
+            for i in 0..cli.frames {
+                app.tick_count += 1;
+                app.animate();
+                let frame = ui::screen_ansi(&mut app, 120, 40);
+                std::fs::write(dir.join(format!("frame-{:04}.ans", i)), frame)?;
+            }
+            println!("wrote {} frames to {}", cli.frames, dir.display());
+            return Ok(());
+        }
+
+        if cli.resize_stress {
+            match ui::stress_resize(&mut app, 120, 40) {
+                Ok(n) => println!("ok: rendered {} layouts", n),
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+
+        let mut terminal = ratatui::init();
+        let result = run(&mut terminal, &api::ApiClient::offline(), None, None, None, app).await;
+        ratatui::restore();
+        return result;
+    }
 
     // Resolve blocknet directory: explicit arg > env var > discovered cookie dir > platform default.
     let mut blocknet_dir = cli
@@ -856,34 +2741,125 @@ async fn main() -> color_eyre::Result<()> {
             std::process::exit(1);
         });
 
-    if !blocknet_dir.exists() {
-        if let Err(e) = std::fs::create_dir_all(&blocknet_dir) {
-            eprintln!(
-                "error: couldn't create Blocknet data directory {}: {}",
-                blocknet_dir.display(),
-                e
-            );
-            std::process::exit(1);
-        }
+    if !blocknet_dir.exists()
+        && let Err(e) = std::fs::create_dir_all(&blocknet_dir)
+    {
+        eprintln!(
+            "error: couldn't create Blocknet data directory {}: {}",
+            blocknet_dir.display(),
+            e
+        );
+        std::process::exit(1);
     }
 
     if let Ok(canonical) = blocknet_dir.canonicalize() {
         blocknet_dir = canonical;
     }
 
+    // one connection string replaces --host/--port (the flag conflicts with
+    // them; the env var loses to them)
+    let api_url = cli.url.clone().or_else(|| {
+        std::env::var("BNTUI_API_URL")
+            .ok()
+            .filter(|u| !u.is_empty() && !explicit("host") && !explicit("port"))
+    });
+    let mut scheme = "http".to_string();
+    if let Some(ref url) = api_url {
+        let parsed = parse_api_url(url).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        });
+        scheme = parsed.scheme;
+        cli.host = parsed.host;
+        cli.port = parsed.port;
+        if cli.cookie.is_none() {
+            cli.cookie = parsed.cookie;
+        }
+    }
+    if cli.tls {
+        scheme = "https".to_string();
+    }
+    // bntui's own daemon serves plain HTTP, so never autostart one for TLS
+    let tls = scheme == "https";
+    if cli.insecure {
+        eprintln!("warning: --insecure: the node's TLS certificate is not verified");
+    }
+    // a token kept out of the process list: --token-file, else the env var
+    // (which, like BNTUI_API_URL, loses to the flags)
+    if let Some(ref path) = cli.token_file {
+        match std::fs::read_to_string(path) {
+            Ok(token) if !token.trim().is_empty() => cli.token = Some(token.trim().to_string()),
+            Ok(_) => {
+                eprintln!("error: {} is empty", path.display());
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("error: can't read {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+    } else if cli.token.is_none() && cli.cookie.is_none() {
+        cli.token = std::env::var("BNTUI_API_TOKEN").ok().filter(|t| !t.is_empty());
+    }
+    // a cookie or token the user gave: no cookie discovery, no autostart
+    let custom_auth = cli.cookie.is_some() || cli.token.is_some();
+
+    // A running daemon may have published where it's listening; use that
+    // unless the user told us where to connect.
+    let mut advertised_cookie = None;
+    if api_url.is_none()
+        && !explicit("host")
+        && !explicit("port")
+        && !custom_auth
+        && let Some(info) = read_daemon_info(&blocknet_dir)
+    {
+        eprintln!("using daemon info: {}", daemon_info_path(&blocknet_dir).display());
+        if let Some(host) = info.host {
+            cli.host = host;
+        }
+        if let Some(port) = info.port {
+            cli.port = port;
+        }
+        advertised_cookie = info.cookie.map(|c| blocknet_dir.join("data").join(c));
+    }
+
     let cookie_path = cli
         .cookie
         .clone()
         .map(PathBuf::from)
+        .or(advertised_cookie)
         .unwrap_or_else(|| blocknet_dir.join("data").join("api.cookie"));
-    let mut base_url = format!("http://{}:{}", cli.host, cli.port);
+    let mut base_url = format!("{}://{}:{}", scheme, cli.host, cli.port);
+    let config = config::Config::load();
+    // a system-wide proxy must not swallow traffic to a local daemon
+    let proxy = if cli.no_proxy {
+        api::ProxyMode::Off
+    } else if let Some(ref url) = cli.proxy {
+        api::ProxyMode::Url(url.clone())
+    } else if is_local_host(&cli.host) {
+        api::ProxyMode::Off
+    } else {
+        api::ProxyMode::Env
+    };
+    let api_config = api::ApiConfig {
+        proxy,
+        token: cli.token.clone(),
+        pool_idle_timeout: std::time::Duration::from_secs(config.connection.pool_idle_secs),
+        pool_max_idle_per_host: config.connection.pool_max_idle,
+        timeout: std::time::Duration::from_secs(config.connection.timeout_secs.max(1)),
+        connect_timeout: std::time::Duration::from_secs(config.connection.connect_timeout_secs.max(1)),
+        accept_invalid_certs: cli.insecure,
+    };
     let mut active_cookie_path = cookie_path.clone();
 
     // If another local Blocknet daemon is already running, try known cookie locations first.
     let mut api = None;
-    if cli.cookie.is_none() && is_local_host(&cli.host) {
+    let mut embedded = None;
+    if !custom_auth && is_local_host(&cli.host) {
         for candidate in discover_cookie_candidates(&cookie_path, &blocknet_dir) {
-            if let Some(client) = try_connect_local_with_cookie(&cli.host, cli.port, &candidate).await {
+            if let Some(client) =
+                try_connect_local_with_cookie(&scheme, &cli.host, cli.port, &candidate, &api_config).await
+            {
                 if candidate != cookie_path {
                     eprintln!("using detected cookie: {}", candidate.display());
                 }
@@ -900,7 +2876,7 @@ async fn main() -> color_eyre::Result<()> {
         let mut launched_embedded = false;
         let mut autostart_port = cli.port;
 
-        if cli.cookie.is_none() && is_local_host(&cli.host) {
+        if !custom_auth && !tls && is_local_host(&cli.host) {
             autostart_port = choose_available_local_port(cli.port).unwrap_or(cli.port);
             if autostart_port != cli.port {
                 eprintln!(
@@ -910,12 +2886,34 @@ async fn main() -> color_eyre::Result<()> {
             }
         }
 
-        if !active_cookie_path.is_file() && cli.cookie.is_none() && is_local_host(&cli.host) {
-            match try_spawn_embedded_daemon(&cli.host, autostart_port, &blocknet_dir) {
-                Ok(path) => {
+        if !active_cookie_path.is_file() && !custom_auth && !tls && is_local_host(&cli.host) {
+            match autostart_embedded_daemon(
+                &cli.host,
+                autostart_port,
+                &blocknet_dir,
+                cli.daemon_binary.as_deref(),
+                &cli.daemon_args,
+            ) {
+                Ok(Autostart::Joined { pid, port }) => {
+                    launched_embedded = true;
+                    base_url = format!("http://{}:{}", cli.host, port);
+                    eprintln!("bntui (pid {}) already runs the embedded daemon here; connecting to it", pid);
+                }
+                Ok(Autostart::Started { path, child, lock }) => {
                     launched_embedded = true;
                     base_url = format!("http://{}:{}", cli.host, autostart_port);
                     eprintln!("started embedded blocknet daemon: {}", path.display());
+                    embedded = Some(EmbeddedDaemon {
+                        child,
+                        host: cli.host.clone(),
+                        port: autostart_port,
+                        blocknet_dir: blocknet_dir.clone(),
+                        binary: cli.daemon_binary.clone(),
+                        args: cli.daemon_args.clone(),
+                        cookie_path: active_cookie_path.clone(),
+                        api_config: api_config.clone(),
+                        _lock: lock,
+                    });
                 }
                 Err(e) => {
                     eprintln!("warning: couldn't start embedded daemon: {e}");
@@ -924,7 +2922,7 @@ async fn main() -> color_eyre::Result<()> {
         }
 
         if launched_embedded {
-            match wait_for_daemon(&base_url, &active_cookie_path, 30).await {
+            match wait_for_daemon(&base_url, &active_cookie_path, &api_config, 30).await {
                 Ok(api) => api,
                 Err(e) => {
                     eprintln!("error: {e}");
@@ -933,7 +2931,7 @@ async fn main() -> color_eyre::Result<()> {
                 }
             }
         } else {
-            if !active_cookie_path.is_file() {
+            if cli.token.is_none() && !active_cookie_path.is_file() {
                 eprintln!("error: cookie file not found: {}", active_cookie_path.display());
                 eprintln!();
                 eprintln!("If this is a local node, bntui can auto-start an embedded daemon when:");
@@ -945,7 +2943,7 @@ async fn main() -> color_eyre::Result<()> {
             }
 
             let cookie_path_str = active_cookie_path.to_string_lossy().into_owned();
-            let api = match api::ApiClient::new(&base_url, &cookie_path_str) {
+            let mut api = match api::ApiClient::new(&base_url, &cookie_path_str, &api_config) {
                 Ok(api) => api,
                 Err(e) => {
                     let err = e.to_string();
@@ -963,13 +2961,75 @@ async fn main() -> color_eyre::Result<()> {
                 }
             };
 
-            if let Err(e) = api.get_status().await {
-                if cli.cookie.is_none() && is_local_host(&cli.host) {
-                    match try_spawn_embedded_daemon(&cli.host, autostart_port, &blocknet_dir) {
-                        Ok(path) => {
+            let mut status = api.get_status().await;
+            if let Err(ref e) = status
+                && api::is_auth_error(e)
+                && cli.token.is_some()
+            {
+                eprintln!("error: authentication failed ({e})");
+                eprintln!("The daemon at {base_url} rejected the API token (--token, --token-file or BNTUI_API_TOKEN).");
+                std::process::exit(1);
+            }
+            if let Err(ref e) = status
+                && api::is_auth_error(e)
+            {
+                // Something is listening and rejected our token: most likely
+                // the daemon restarted and rewrote its cookie after we read it.
+                // Read it again rather than starting a second daemon.
+                eprintln!("authentication failed — cookie may be stale; re-reading {}", cookie_path_str);
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                if let Ok(fresh) = api::ApiClient::new(&base_url, &cookie_path_str, &api_config) {
+                    status = fresh.get_status().await;
+                    api = fresh;
+                }
+                if let Err(ref e) = status
+                    && api::is_auth_error(e)
+                {
+                    eprintln!("error: authentication failed — cookie may be stale ({e})");
+                    eprintln!();
+                    eprintln!("The daemon at {base_url} rejected the token in:");
+                    eprintln!("  {}", active_cookie_path.display());
+                    eprintln!("Is this cookie from a different data directory or an older daemon run?");
+                    std::process::exit(1);
+                }
+            }
+
+            if let Err(e) = status {
+                if !custom_auth && !tls && is_local_host(&cli.host) {
+                    match autostart_embedded_daemon(
+                        &cli.host,
+                        autostart_port,
+                        &blocknet_dir,
+                        cli.daemon_binary.as_deref(),
+                        &cli.daemon_args,
+                    ) {
+                        Ok(Autostart::Joined { pid, port }) => {
+                            base_url = format!("http://{}:{}", cli.host, port);
+                            eprintln!("bntui (pid {}) already runs the embedded daemon here; connecting to it", pid);
+                            match wait_for_daemon(&base_url, &active_cookie_path, &api_config, 30).await {
+                                Ok(api) => api,
+                                Err(wait_err) => {
+                                    eprintln!("error: {wait_err}");
+                                    eprintln!("initial API error: {e}");
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                        Ok(Autostart::Started { path, child, lock }) => {
                             base_url = format!("http://{}:{}", cli.host, autostart_port);
                             eprintln!("started embedded blocknet daemon: {}", path.display());
-                            match wait_for_daemon(&base_url, &active_cookie_path, 30).await {
+                            embedded = Some(EmbeddedDaemon {
+                                child,
+                                host: cli.host.clone(),
+                                port: autostart_port,
+                                blocknet_dir: blocknet_dir.clone(),
+                                binary: cli.daemon_binary.clone(),
+                                args: cli.daemon_args.clone(),
+                                cookie_path: active_cookie_path.clone(),
+                                api_config: api_config.clone(),
+                                _lock: lock,
+                            });
+                            match wait_for_daemon(&base_url, &active_cookie_path, &api_config, 30).await {
                                 Ok(api) => api,
                                 Err(wait_err) => {
                                     eprintln!("error: {wait_err}");
@@ -988,6 +3048,9 @@ async fn main() -> color_eyre::Result<()> {
                 } else {
                     eprintln!("error: could not connect to Blocknet daemon at {base_url}");
                     eprintln!("  {e}");
+                    if tls && !cli.insecure {
+                        eprintln!("If the node uses a self-signed certificate, add --insecure.");
+                    }
                     std::process::exit(1);
                 }
             } else {
@@ -996,8 +3059,141 @@ async fn main() -> color_eyre::Result<()> {
         }
     };
 
+    let mut app = app::App::new();
+    app.theme = theme::Theme::from_config(&config.theme);
+    app.config = config;
+    if let Some(url) = cli.explorer_url.clone() {
+        app.config.explorer.block_url = url;
+    }
+    if cli.no_tx_log {
+        app.config.wallet.tx_log = false;
+    }
+    // a command-line price source replaces the configured one
+    if cli.price.is_some() || cli.price_url.is_some() {
+        app.config.fiat.rate = cli.price;
+        app.config.fiat.url = cli.price_url.clone();
+    }
+    app.fiat_rate = app.config.fiat.rate.filter(|r| r.is_finite() && *r >= 0.0);
+    // the price server is a third party, never the local daemon, so only
+    // --no-proxy turns the environment's proxy off for it
+    let price_proxy = if cli.no_proxy {
+        api::ProxyMode::Off
+    } else if let Some(ref url) = cli.proxy {
+        api::ProxyMode::Url(url.clone())
+    } else {
+        api::ProxyMode::Env
+    };
+    let price_interval = std::time::Duration::from_secs(app.config.fiat.refresh_secs.max(1));
+    let price_feed = match app.config.fiat.url {
+        Some(ref url) => match api::PriceFeed::start(url, &price_proxy, price_interval) {
+            Ok(feed) => Some(feed),
+            Err(e) => {
+                eprintln!("warning: ignoring price URL: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+    if app.config.storage.encrypt {
+        match unlock_storage() {
+            Ok(key) => app.cipher = Some(key),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    app.load_state();
+    app.load_mined_log();
+    app.address_book = addressbook::AddressBook::load(app.cipher.as_ref());
+    if let Some(view) = cli.view {
+        app.current_view = match view {
+            StartView::Dashboard => 1,
+            StartView::Grid => 2,
+            StartView::Peers => 3,
+            StartView::History => 4,
+        };
+    }
+    app.split_view = cli.split;
+    app.accessible = cli.accessible;
+    app.cube_style = cli.cube_style;
+    app.export_format = cli.export_format;
+    app.readonly = cli.readonly;
+    app.target_block_time_secs = cli.target_block_time;
+    if let Some(ticks) = cli.status_interval {
+        app.status_poll_ticks = ticks;
+    }
+    if let Some(ticks) = cli.data_interval {
+        app.data_poll_ticks = ticks;
+    }
+    app.history_len = cli
+        .history_len
+        .map_or_else(|| app.config.display.history_len(), |n| n as usize);
+    app.blocknet_dir = Some(blocknet_dir.clone());
+    // short terminals can't fit the full block info under the cube
+    if let Ok((_, rows)) = crossterm::terminal::size()
+        && rows < 30
+    {
+        app.info_density = app::InfoDensity::Minimal;
+    }
+    app.backfill_depth = cli.blocks.unwrap_or_else(|| match crossterm::terminal::size() {
+        // two screens' worth, so paging back once doesn't run out
+        Ok((w, h)) => (ui::chain::grid_capacity(w, h) as u64 * 2).clamp(100, 2000),
+        Err(_) => app::DEFAULT_BACKFILL,
+    });
+    app.max_blocks = cli
+        .max_blocks
+        .or(app.config.display.max_blocks)
+        .unwrap_or(app::DEFAULT_MAX_BLOCKS)
+        .max(app::MIN_MAX_BLOCKS);
+    if app.backfill_depth > app.max_blocks {
+        eprintln!(
+            "warning: --blocks {} is more than the {} blocks kept in memory; loading {}",
+            app.backfill_depth, app.max_blocks, app.max_blocks
+        );
+        app.backfill_depth = app.max_blocks;
+    }
+
+    if cli.mine {
+        match cli.mine_threads {
+            Some(MineThreads::Fixed(n)) => app.mine_threads = Some(n),
+            Some(MineThreads::Auto) => app.mine_threads = None,
+            None => {}
+        }
+        let threads = app.mine_threads.unwrap_or_else(auto_mine_threads);
+        api.set_threads(threads).await.ok();
+        match api.start_mining().await {
+            Ok(()) => app.event(format!("mining started on launch with {} threads", threads)),
+            Err(e) => app.event(format!("could not start mining: {}", e)),
+        }
+    }
+
+    let exposer = match cli.expose {
+        Some(addr) => {
+            if !addr.ip().is_loopback() {
+                eprintln!("warning: --expose {} is reachable from other machines", addr);
+            }
+            match expose::Exposer::start(addr) {
+                Ok(exposer) => Some(exposer),
+                Err(e) => {
+                    eprintln!("error: --expose: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
+
     let mut terminal = ratatui::init();
-    let result = run(&mut terminal, &api).await;
+    let result = run(
+        &mut terminal,
+        &api,
+        price_feed.as_ref(),
+        exposer.as_ref(),
+        embedded,
+        app,
+    )
+    .await;
     ratatui::restore();
 
     result
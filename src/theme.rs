@@ -0,0 +1,44 @@
+//! `[theme]` in the config: the colors every view draws with. Kept out of
+//! `ui` so `App` can hold the palette without depending on the renderer.
+
+use ratatui::style::Color;
+
+/// The palette every view draws with, from `[theme]` in the config.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub primary: Color,
+    pub dim: Color,
+    pub accent: Color,
+    pub warning: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary: Color::Rgb(170, 255, 0),
+            dim: Color::Rgb(140, 140, 140),
+            accent: Color::Rgb(0, 200, 255),
+            warning: Color::Yellow,
+        }
+    }
+}
+
+impl Theme {
+    /// Colors set in `config`, with the defaults for unset or invalid
+    /// ones (`Config::load` has already warned about those).
+    pub fn from_config(config: &crate::config::ThemeConfig) -> Self {
+        let pick = |value: &Option<String>, default: Color| {
+            value
+                .as_deref()
+                .and_then(crate::config::parse_hex_color)
+                .map_or(default, |(r, g, b)| Color::Rgb(r, g, b))
+        };
+        let default = Self::default();
+        Self {
+            primary: pick(&config.primary, default.primary),
+            dim: pick(&config.dim, default.dim),
+            accent: pick(&config.accent, default.accent),
+            warning: pick(&config.warning, default.warning),
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct DaemonStats {
     pub peer_id: String,
@@ -18,9 +18,12 @@ pub struct DaemonStats {
     #[serde(default)]
     pub sync_percent: Option<String>,
     pub identity_age: String,
+    // only reported by some daemon versions
+    #[serde(default)]
+    pub version: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct MempoolStats {
     pub count: u32,
@@ -30,7 +33,41 @@ pub struct MempoolStats {
     pub avg_fee: f64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl MempoolStats {
+    /// Average fee per byte of the pending transactions, or `None` while
+    /// the mempool is empty.
+    pub fn avg_fee_rate(&self) -> Option<f64> {
+        if self.count == 0 || self.size_bytes == 0 {
+            return None;
+        }
+        Some(self.avg_fee * self.count as f64 / self.size_bytes as f64)
+    }
+}
+
+/// One pending transaction from `/api/mempool/txs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolTx {
+    #[serde(alias = "hash", alias = "id")]
+    pub txid: String,
+    #[serde(default)]
+    pub fee: u64,
+    #[serde(default, alias = "size_bytes")]
+    pub size: u64,
+}
+
+/// Suggested fees from `/api/fee/estimate`, in atomic units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(dead_code)]
+pub struct FeeEstimate {
+    #[serde(default, alias = "slow")]
+    pub low: u64,
+    #[serde(alias = "medium", alias = "fee")]
+    pub normal: u64,
+    #[serde(default, alias = "fast")]
+    pub high: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct BalanceResponse {
     pub spendable: u64,
@@ -41,12 +78,12 @@ pub struct BalanceResponse {
     pub chain_height: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddressResponse {
     pub address: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct MiningStatus {
     pub running: bool,
@@ -61,16 +98,133 @@ pub struct MiningStatus {
     pub started_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(dead_code)]
+pub struct PeerInfo {
+    #[serde(default, alias = "addr")]
+    pub address: String,
+    #[serde(default)]
+    pub direction: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub ping: Option<f64>,
+}
+
+impl PeerInfo {
+    pub fn is_inbound(&self) -> bool {
+        self.direction.to_ascii_lowercase().starts_with("in")
+    }
+
+    pub fn is_outbound(&self) -> bool {
+        self.direction.to_ascii_lowercase().starts_with("out")
+    }
+
+    /// IP part of `address`. Accepts `ip:port`, `[v6]:port`, a bare IP, or a
+    /// multiaddr like `/ip4/1.2.3.4/tcp/28080/p2p/…`; anything else is `None`.
+    pub fn ip(&self) -> Option<std::net::IpAddr> {
+        let addr = self.address.trim();
+        if addr.starts_with('/') {
+            let mut parts = addr.split('/');
+            while let Some(proto) = parts.next() {
+                if proto == "ip4" || proto == "ip6" {
+                    return parts.next()?.parse().ok();
+                }
+            }
+            return None;
+        }
+        addr.parse::<std::net::SocketAddr>()
+            .map(|sa| sa.ip())
+            .or_else(|_| addr.parse())
+            .ok()
+    }
+}
+
+/// Peer list aggregated by direction and subnet (IPv4 /16, IPv6 /32).
+pub struct PeerSummary {
+    pub inbound: usize,
+    pub outbound: usize,
+    // direction missing or not understood
+    pub other: usize,
+    // (subnet, peers), largest first
+    pub subnets: Vec<(String, usize)>,
+    // addresses that aren't an IP (onion, DNS name, empty…)
+    pub unparsed: usize,
+}
+
+pub fn summarize_peers(peers: &[PeerInfo]) -> PeerSummary {
+    let mut summary = PeerSummary {
+        inbound: 0,
+        outbound: 0,
+        other: 0,
+        subnets: vec![],
+        unparsed: 0,
+    };
+    let mut subnets: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for peer in peers {
+        if peer.is_inbound() {
+            summary.inbound += 1;
+        } else if peer.is_outbound() {
+            summary.outbound += 1;
+        } else {
+            summary.other += 1;
+        }
+        let subnet = match peer.ip() {
+            Some(std::net::IpAddr::V4(ip)) => {
+                let o = ip.octets();
+                format!("{}.{}.0.0/16", o[0], o[1])
+            }
+            Some(std::net::IpAddr::V6(ip)) => {
+                let s = ip.segments();
+                format!("{:x}:{:x}::/32", s[0], s[1])
+            }
+            None => {
+                summary.unparsed += 1;
+                continue;
+            }
+        };
+        *subnets.entry(subnet).or_default() += 1;
+    }
+    summary.subnets = subnets.into_iter().collect();
+    summary.subnets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    summary
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockTransaction {
     pub hash: String,
     pub fee: u64,
     pub inputs: u32,
     pub outputs: u32,
     pub is_coinbase: bool,
+    // value of all outputs; only reported by newer daemons
+    #[serde(default)]
+    pub total_out: Option<u64>,
+    // serialized size; only reported by newer daemons
+    #[serde(default, alias = "size_bytes")]
+    pub size: Option<u64>,
 }
 
+/// One input or output of `/api/tx/{txid}`. Amounts are only known to the
+/// node for outputs its wallet owns; the rest stay hidden.
 #[derive(Debug, Clone, Deserialize)]
+pub struct TxIo {
+    #[serde(default)]
+    pub amount: Option<u64>,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub key_image: Option<String>,
+}
+
+/// Per-input/output detail of a transaction, for the tx detail view.
+#[derive(Debug, Clone, Default)]
+pub struct TxDetail {
+    pub inputs: Vec<TxIo>,
+    pub outputs: Vec<TxIo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct BlockResponse {
     pub height: u64,
@@ -112,6 +266,53 @@ pub fn format_bnt(atomic: u64) -> String {
     }
 }
 
+/// Expected number of blocks found after `hashes` attempts at `difficulty`
+/// (one block per `difficulty` hashes on average). Zero when either is zero.
+pub fn expected_blocks(hashes: f64, difficulty: u64) -> f64 {
+    if difficulty == 0 || hashes <= 0.0 {
+        return 0.0;
+    }
+    hashes / difficulty as f64
+}
+
+/// Blocks found vs expected from `hashes` attempts, as a percentage, or
+/// `None` before any work has been done.
+pub fn mining_luck(blocks_found: u64, hashes: u64, difficulty: u64) -> Option<f64> {
+    let expected = expected_blocks(hashes as f64, difficulty);
+    if expected <= 0.0 {
+        return None;
+    }
+    Some(blocks_found as f64 / expected * 100.0)
+}
+
+/// Like `format_bnt`, but always with exactly `decimals` (0–8) fractional
+/// digits, so columns of amounts line up.
+pub fn format_bnt_fixed(atomic: u64, decimals: u8) -> String {
+    let decimals = decimals.min(8) as u32;
+    let whole = atomic / 100_000_000;
+    if decimals == 0 {
+        return format!("{} BNT", whole);
+    }
+    let frac = (atomic % 100_000_000) / 10u64.pow(8 - decimals);
+    format!("{}.{:0w$} BNT", whole, frac, w = decimals as usize)
+}
+
+/// Approximate fiat value of `atomic` BNT at `rate` per BNT, e.g.
+/// "≈ $1,234.56".
+pub fn format_fiat(atomic: u64, rate: f64, symbol: &str) -> String {
+    let value = atomic as f64 / 100_000_000.0 * rate;
+    let cents = (value * 100.0).round() as u64;
+    let whole = (cents / 100).to_string();
+    let mut grouped = String::new();
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    format!("≈ {}{}.{:02}", symbol, grouped, cents % 100)
+}
+
 pub fn parse_bnt_amount(s: &str) -> Option<u64> {
     let s = s.trim();
     if s.is_empty() {
@@ -136,3 +337,145 @@ pub fn parse_bnt_amount(s: &str) -> Option<u64> {
         _ => None,
     }
 }
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Whether `s` looks like an address the daemon will accept: base58 (no
+/// prefix or checksum) of the 32-byte spend and view public keys, the way
+/// the daemon's own parser decodes it. Catches typos, not unknown keys.
+pub fn is_valid_address(s: &str) -> bool {
+    if s.is_empty() {
+        return false;
+    }
+    // little-endian base-256 digits of the decoded value
+    let mut value: Vec<u8> = Vec::new();
+    for c in s.bytes() {
+        let Some(digit) = BASE58_ALPHABET.iter().position(|&a| a == c) else {
+            return false;
+        };
+        let mut carry = digit as u32;
+        for byte in value.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            value.push(carry as u8);
+            carry >>= 8;
+        }
+        if value.len() > 64 {
+            return false;
+        }
+    }
+    // each leading '1' stands for a zero byte
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    zeros + value.len() == 64
+}
+
+/// `major.minor.patch` out of a daemon version string such as "v0.3.1" or
+/// "0.3.1-rc2 (abc123)". A missing patch counts as 0.
+pub fn parse_version(s: &str) -> Option<(u32, u32, u32)> {
+    let s = s.trim().trim_start_matches('v');
+    let end = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let mut parts = s[..end].split('.').map(|p| p.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+/// Whether `s` is a transaction hash as the daemon prints it: 64 hex digits.
+pub fn is_valid_txid(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expected_blocks, is_valid_address, is_valid_txid, mining_luck, parse_version};
+
+    // base58 of the bytes 1..=64
+    const ADDR: &str =
+        "2Ana1pUpv2ZbMVkwF5FXapYeBEjdxDatLn7nvJkhgTSXbs59SyZSx866bXirPgj8QQVB57uxHJBG1YFvkRbFj4T";
+
+    // all 0xff bytes, the largest value that fits
+    const MAX: &str =
+        "67rpwLCuS5DGA8KGZXKsVQ7dnPb9goRLoKfgGbLfQg9WoLUgNY77E2jT11fem3coV9nAkguBACzrU1iyZM4B8roQ";
+
+    #[test]
+    fn accepts_valid_addresses() {
+        assert!(is_valid_address(ADDR));
+        // a leading zero byte encodes as a leading '1'
+        assert!(is_valid_address(
+            "1GMkH3brNXiNNs1tiFZHu4yZSRrzJwxi5wB9bHFtMinfCXNnR1adh8Vo8NTheK4evneedH4qmvjeqcBBNAefgS"
+        ));
+        assert!(is_valid_address(MAX));
+    }
+
+    #[test]
+    fn rejects_wrong_prefix() {
+        // an extra leading '1' adds a zero byte
+        assert!(!is_valid_address(&format!("1{}", ADDR)));
+        // a Bitcoin address: versioned and checksummed, 25 bytes
+        assert!(!is_valid_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"));
+        assert!(!is_valid_address(&format!("bnt{}", ADDR)));
+    }
+
+    #[test]
+    fn rejects_bad_length() {
+        assert!(!is_valid_address(""));
+        assert!(!is_valid_address(&ADDR[..ADDR.len() - 1]));
+        assert!(!is_valid_address(&format!("{}2", MAX)));
+        // a single 32-byte key
+        assert!(!is_valid_address("4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw"));
+    }
+
+    #[test]
+    fn rejects_illegal_characters() {
+        for bad in ['0', 'O', 'I', 'l', ' ', '-', 'é'] {
+            let addr = format!("{}{}", &ADDR[..40], bad) + &ADDR[41..];
+            assert!(!is_valid_address(&addr), "accepted {:?}", bad);
+        }
+    }
+
+    #[test]
+    fn txids_are_64_hex_digits() {
+        assert!(is_valid_txid(&"ab".repeat(32)));
+        assert!(is_valid_txid(&"AB09".repeat(16)));
+        assert!(!is_valid_txid(&"ab".repeat(31)));
+        assert!(!is_valid_txid(&"ab".repeat(33)));
+        assert!(!is_valid_txid(&format!("{}g", "a".repeat(63))));
+        assert!(!is_valid_txid("../api/status"));
+    }
+
+    #[test]
+    fn parses_daemon_versions() {
+        assert_eq!(parse_version("0.3.1"), Some((0, 3, 1)));
+        assert_eq!(parse_version("v1.2"), Some((1, 2, 0)));
+        assert_eq!(parse_version("0.4.0-rc2 (abc123)"), Some((0, 4, 0)));
+        assert_eq!(parse_version("blocknet"), None);
+        assert_eq!(parse_version("1"), None);
+    }
+
+    #[test]
+    fn no_hashrate_expects_nothing() {
+        assert_eq!(expected_blocks(0.0, 100_000), 0.0);
+        assert_eq!(mining_luck(0, 0, 100_000), None);
+        assert_eq!(mining_luck(3, 0, 100_000), None);
+    }
+
+    #[test]
+    fn zero_difficulty_expects_nothing() {
+        assert_eq!(expected_blocks(1_000_000.0, 0), 0.0);
+        assert_eq!(mining_luck(1, 1_000_000, 0), None);
+    }
+
+    #[test]
+    fn luck_is_found_over_expected() {
+        // 1 kH/s for a day at difficulty 864k: 100 blocks
+        assert_eq!(expected_blocks(1_000.0 * 86_400.0, 864_000), 100.0);
+        assert_eq!(expected_blocks(1_000_000.0, 100_000), 10.0);
+        assert_eq!(mining_luck(5, 1_000_000, 100_000), Some(50.0));
+        assert_eq!(mining_luck(10, 1_000_000, 100_000), Some(100.0));
+        assert_eq!(mining_luck(0, 1_000_000, 100_000), Some(0.0));
+    }
+}
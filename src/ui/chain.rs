@@ -4,18 +4,22 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use crate::{app::App};
-use crate::types::{format_bnt, format_time_ago};
-use super::{GREEN, DIM};
+use crate::app::{App, GridCellStyle, InfoDensity, LeftPanelMode};
+use crate::theme::Theme;
+use crate::types::format_time_ago;
+use super::tx_fee_color;
 
 // Each cell: 2-char block + 1 gap = 3 cols, 1 row tall
 const BLOCK_W: u16 = 2;
 const CELL_W: u16 = 3;
+// bar-style cells, empty block → busiest block
+const BAR_GLYPHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
 pub fn render(frame: &mut Frame, app: &mut App, title_area: Rect, content_area: Rect) {
+    let theme = app.theme;
     // single green border around the whole view
     let full = Rect {
         x: title_area.x,
@@ -24,10 +28,18 @@ pub fn render(frame: &mut Frame, app: &mut App, title_area: Rect, content_area:
         height: title_area.height + content_area.height,
     };
 
+    let title = if app.show_only_mine {
+        match app.mined_in_view() {
+            0 => " Grid · none of your blocks loaded ".to_string(),
+            n => format!(" Grid · {} of your blocks in view ", n),
+        }
+    } else {
+        " Grid ".to_string()
+    };
     let border = Block::default()
-        .title(" Grid ")
+        .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(GREEN));
+        .border_style(Style::default().fg(theme.primary));
     let inner = border.inner(full);
     frame.render_widget(border, full);
 
@@ -43,11 +55,23 @@ pub fn render(frame: &mut Frame, app: &mut App, title_area: Rect, content_area:
     .split(inner);
 
     render_main_area(frame, app, sections[0]);
-    render_hrule(frame, sections[1]);
+    render_hrule(frame, sections[1], theme);
     render_progress_bar(frame, app, sections[2]);
 }
 
-fn render_tx_list(frame: &mut Frame, block: &crate::types::BlockResponse, area: Rect) {
+/// Roughly how many blocks the grid shows at once on a `width`×`height`
+/// terminal, following the layout in `render` and `render_main_area`.
+pub fn grid_capacity(width: u16, height: u16) -> usize {
+    let inner_w = width.saturating_sub(2);
+    // left panel 35%, separator, 7-digit gutter, scrollbar margin
+    let grid_w = inner_w.saturating_sub(inner_w * 35 / 100 + 1 + 8);
+    // help bar, borders, rule and progress bar
+    let grid_h = height.saturating_sub(5);
+    (grid_w / CELL_W) as usize * (grid_h as usize).div_ceil(2)
+}
+
+fn render_tx_list(frame: &mut Frame, app: &App, block: &crate::types::BlockResponse, area: Rect) {
+    let theme = app.theme;
     if area.height == 0 {
         return;
     }
@@ -55,39 +79,95 @@ fn render_tx_list(frame: &mut Frame, block: &crate::types::BlockResponse, area:
     let mut lines = Vec::new();
     let max_txs = area.height as usize;
 
-    for (i, tx) in block.transactions.iter().enumerate() {
-        if i >= max_txs {
+    // scroll so a highlighted tx (from find-tx) is on screen, else to the
+    // PageUp/PageDown offset
+    let highlight = app
+        .highlight_txid
+        .as_deref()
+        .and_then(|id| block.transactions.iter().position(|tx| tx.hash == id));
+    let start = highlight.map_or_else(
+        || app.tx_scroll().min(block.transactions.len().saturating_sub(max_txs)),
+        |i| (i + 2).saturating_sub(max_txs).min(i),
+    );
+
+    // value moved per tx, right-aligned to the widest shown, when the daemon
+    // reports it; the coinbase moves the block reward
+    let shown = || block.transactions.iter().skip(start).take(max_txs);
+    let moved = |tx: &crate::types::BlockTransaction| {
+        if tx.is_coinbase { Some(block.reward) } else { tx.total_out }
+    };
+    let amount_w = shown()
+        .filter_map(|tx| moved(tx).map(|v| app.fmt_bnt(v).chars().count()))
+        .max()
+        .unwrap_or(0);
+    // hash, amount, fee and in/out don't fit the narrow panel together
+    let with_counts = amount_w == 0 || area.width as usize >= 40 + amount_w;
+
+    for (i, tx) in block.transactions.iter().enumerate().skip(start) {
+        if i - start >= max_txs {
             break;
         }
 
-        let hash_short = &tx.hash[..tx.hash.len().min(10)];
+        // short enough to leave room for the amount column
+        let hash_short = &tx.hash[..tx.hash.len().min(7)];
+        let amount = moved(tx).map(|v| format!("{:>w$} ", app.fmt_bnt(v), w = amount_w));
 
         if tx.is_coinbase {
-            lines.push(Line::from(vec![
-                Span::styled(" coinbase ", Style::default().fg(GREEN)),
-                Span::styled(
-                    format!("{}in {}out", tx.inputs, tx.outputs),
-                    Style::default().fg(DIM),
-                ),
-            ]));
+            let label_style = if highlight == Some(i) {
+                Style::default().fg(Color::Black).bg(theme.primary)
+            } else {
+                Style::default().fg(theme.primary)
+            };
+            let mut spans = vec![Span::styled(" coinbase ", label_style)];
+            if let Some(amount) = amount {
+                spans.push(Span::styled(amount, Style::default().fg(Color::White)));
+            }
+            spans.push(Span::styled(
+                format!("{}in {}out", tx.inputs, tx.outputs),
+                Style::default().fg(theme.dim),
+            ));
+            lines.push(Line::from(spans));
+        } else if let Some(amount) = amount {
+            let hash_style = if highlight == Some(i) {
+                Style::default().fg(Color::Black).bg(theme.primary)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {}… ", hash_short), hash_style),
+                Span::styled(amount, Style::default().fg(Color::White)),
+                Span::styled(app.fmt_bnt(tx.fee), Style::default().fg(tx_fee_color(tx.fee, tx.size, theme.dim))),
+            ];
+            if with_counts {
+                spans.push(Span::styled(
+                    format!(" {}->{}", tx.inputs, tx.outputs),
+                    Style::default().fg(theme.dim),
+                ));
+            }
+            lines.push(Line::from(spans));
         } else {
+            let hash_style = if highlight == Some(i) {
+                Style::default().fg(Color::Black).bg(theme.primary)
+            } else {
+                Style::default().fg(Color::White)
+            };
             lines.push(Line::from(vec![
-                Span::styled(format!(" {}... ", hash_short), Style::default().fg(Color::White)),
-                Span::styled(format_bnt(tx.fee), Style::default().fg(DIM)),
+                Span::styled(format!(" {}… ", hash_short), hash_style),
+                Span::styled(app.fmt_bnt(tx.fee), Style::default().fg(tx_fee_color(tx.fee, tx.size, theme.dim))),
                 Span::styled(
                     format!(" {}->{}", tx.inputs, tx.outputs),
-                    Style::default().fg(DIM),
+                    Style::default().fg(theme.dim),
                 ),
             ]));
         }
     }
 
-    if block.transactions.len() > max_txs {
+    if block.transactions.len() > start + max_txs {
         // more..
         if let Some(last) = lines.last_mut() {
             *last = Line::from(Span::styled(
-                format!(" +{} more...", block.transactions.len() - max_txs + 1),
-                Style::default().fg(DIM),
+                format!(" +{} more...", block.transactions.len() - start - max_txs + 1),
+                Style::default().fg(theme.dim),
             ));
         }
     }
@@ -95,6 +175,106 @@ fn render_tx_list(frame: &mut Frame, block: &crate::types::BlockResponse, area:
     frame.render_widget(Paragraph::new(lines), area);
 }
 
+/// Full-screen detail of the transaction opened with Enter from the tx list.
+pub fn render_tx_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme;
+    let crate::app::InputMode::TxDetail {
+        block_height,
+        tx_index,
+        ref detail,
+        scroll,
+    } = app.input_mode
+    else {
+        return;
+    };
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .title(" Transaction ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.primary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height == 0 {
+        return;
+    }
+
+    let label = |s: &str| Span::styled(format!(" {:<10}", s), Style::default().fg(theme.dim));
+    let white = |s: String| Span::styled(s, Style::default().fg(Color::White));
+    let tx = app
+        .chain_blocks
+        .iter()
+        .find(|b| b.height == block_height)
+        .and_then(|b| b.transactions.get(tx_index).map(|tx| (b, tx)));
+    let mut lines = Vec::new();
+    match tx {
+        None => lines.push(Line::from(Span::styled(
+            format!(" Block #{} is no longer loaded", block_height),
+            Style::default().fg(theme.dim),
+        ))),
+        Some((block, tx)) => {
+            // the full hash, broken over as many lines as the width needs
+            let hash_w = (inner.width as usize).saturating_sub(12).max(8);
+            for (i, chunk) in tx.hash.as_bytes().chunks(hash_w).enumerate() {
+                let chunk = String::from_utf8_lossy(chunk).into_owned();
+                lines.push(Line::from(vec![label(if i == 0 { "Hash" } else { "" }), white(chunk)]));
+            }
+            lines.push(Line::from(vec![
+                label("Block"),
+                white(format!("#{} (tx {} of {})", block.height, tx_index + 1, block.transactions.len())),
+            ]));
+            lines.push(Line::from(vec![
+                label("Coinbase"),
+                white(if tx.is_coinbase { "yes".to_string() } else { "no".to_string() }),
+            ]));
+            lines.push(Line::from(vec![
+                label("Fee"),
+                Span::styled(app.fmt_bnt(tx.fee), Style::default().fg(tx_fee_color(tx.fee, tx.size, theme.dim))),
+            ]));
+            let total_out = if tx.is_coinbase { Some(block.reward) } else { tx.total_out };
+            if let Some(v) = total_out {
+                lines.push(Line::from(vec![label("Total out"), white(app.fmt_bnt(v))]));
+            }
+
+            let io_line = |io: &crate::types::TxIo| {
+                let amount = io.amount.map_or("hidden".to_string(), |v| app.fmt_bnt(v));
+                let id = io.address.as_deref().or(io.key_image.as_deref()).unwrap_or("");
+                Line::from(vec![
+                    Span::raw("   "),
+                    Span::styled(
+                        format!("{:<20} ", amount),
+                        Style::default().fg(if io.amount.is_some() { Color::White } else { theme.dim }),
+                    ),
+                    Span::styled(id.to_string(), Style::default().fg(theme.dim)),
+                ])
+            };
+            let (inputs, outputs) = match detail {
+                Ok(d) => (d.inputs.as_slice(), d.outputs.as_slice()),
+                Err(_) => (&[][..], &[][..]),
+            };
+            for (name, count, list) in [("Inputs", tx.inputs, inputs), ("Outputs", tx.outputs, outputs)] {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![label(name), white(count.to_string())]));
+                lines.extend(list.iter().map(io_line));
+            }
+            if let Err(e) = detail {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!(" Input/output detail unavailable: {}", e),
+                    Style::default().fg(theme.dim),
+                )));
+            }
+        }
+    }
+
+    let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+    let max_scroll = (lines.len() as u16).saturating_sub(rows[0].height);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll.min(max_scroll), 0)), rows[0]);
+    frame.render_widget(
+        Paragraph::new(Span::styled(" c copy hash · j/k scroll · Esc back", Style::default().fg(theme.dim))),
+        rows[1],
+    );
+}
+
 // ── Left panel: cube + block info + block time bar ──
 
 fn render_main_area(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -106,44 +286,67 @@ fn render_main_area(frame: &mut Frame, app: &mut App, area: Rect) {
     .split(area);
 
     render_left_panel(frame, app, cols[0]);
-    render_separator(frame, cols[1]);
+    render_separator(frame, cols[1], app.theme);
     render_block_grid(frame, app, cols[2]);
 }
 
 fn render_left_panel(frame: &mut Frame, app: &mut App, area: Rect) {
-    let sections = Layout::vertical([
-        Constraint::Min(1),
-        Constraint::Length(7),
-        Constraint::Length(1),
-        Constraint::Min(1),
-    ])
-    .split(area);
+    let theme = app.theme;
+    let info_lines = block_info_lines(app, area.width as usize);
+    let info_height = info_lines.len() as u16 + 1;
+    // hidden parts get no rows so the section indices stay put
+    let constraints = match app.left_panel_mode {
+        LeftPanelMode::Balanced => [
+            Constraint::Min(1),
+            Constraint::Length(info_height),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ],
+        LeftPanelMode::Cube => [
+            Constraint::Min(1),
+            Constraint::Length(0),
+            Constraint::Length(0),
+            Constraint::Length(0),
+        ],
+        LeftPanelMode::Info => [
+            Constraint::Length(0),
+            Constraint::Length(info_height),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ],
+    };
+    let sections = Layout::vertical(constraints).split(area);
 
     // spinning cube
-    if app.selected < app.block_cubes.len() {
+    if !app.accessible && !sections[0].is_empty() && app.selected < app.block_cubes.len() {
         let cube = &mut app.block_cubes[app.selected];
-        cube.color = GREEN;
+        cube.color = theme.primary;
         cube.frozen = false;
+        cube.style = app.cube_style;
         frame.render_widget(&mut *cube, sections[0]);
     }
 
     // block info below cube
-    render_block_info(frame, app, sections[1]);
+    frame.render_widget(Paragraph::new(info_lines), sections[1]);
+    app.tx_list_rows = sections[3].height as usize;
 
     let block = app.chain_blocks.get(app.selected);
-    if let Some(block) = block {
-    let rule: String = "─".repeat(sections[2].width as usize);
-    frame.render_widget(
-        Paragraph::new(Span::styled(rule, Style::default().fg(DIM))),
-        sections[2],
-    );
-    render_tx_list(frame,block,sections[3]);
+    if let Some(block) = block
+        && !sections[3].is_empty()
+    {
+        let rule: String = "─".repeat(sections[2].width as usize);
+        frame.render_widget(
+            Paragraph::new(Span::styled(rule, Style::default().fg(theme.dim))),
+            sections[2],
+        );
+        render_tx_list(frame, app, block, sections[3]);
     }
 }
 
-fn render_block_info(frame: &mut Frame, app: &App, area: Rect) {
+fn block_info_lines(app: &App, width: usize) -> Vec<Line<'static>> {
+    let theme = app.theme;
     let Some(block) = app.chain_blocks.get(app.selected) else {
-        return;
+        return vec![];
     };
 
     let block_time_secs = if app.selected > 0 {
@@ -154,48 +357,57 @@ fn render_block_info(frame: &mut Frame, app: &App, area: Rect) {
         None
     };
 
-    let w = area.width as usize;
+    let w = width;
     let rule: String = "─".repeat(w.saturating_sub(2));
 
     // header: block height
     let header = Line::from(vec![
-        Span::styled(" Block ", Style::default().fg(DIM)),
+        Span::styled(" Block ", Style::default().fg(theme.dim)),
         Span::styled(
             format!("#{}", block.height),
-            Style::default().fg(GREEN).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
         ),
     ]);
 
+    if app.info_density == InfoDensity::Minimal {
+        let mut header = header;
+        header.push_span(Span::styled(
+            format!("  {}", format_time_ago(block.timestamp)),
+            Style::default().fg(Color::White),
+        ));
+        return vec![header];
+    }
+
     // separator
-    let sep = Line::from(Span::styled(format!(" {}", rule), Style::default().fg(DIM)));
+    let sep = Line::from(Span::styled(format!(" {}", rule), Style::default().fg(theme.dim)));
 
     // row 1: txs + reward
     let row1 = Line::from(vec![
-        Span::styled(" Txs ", Style::default().fg(DIM)),
+        Span::styled(" Txs ", Style::default().fg(theme.dim)),
         Span::styled(format!("{:<6}", block.tx_count), Style::default().fg(Color::White)),
-        Span::styled("Reward ", Style::default().fg(DIM)),
-        Span::styled(format_bnt(block.reward), Style::default().fg(GREEN)),
+        Span::styled("Reward ", Style::default().fg(theme.dim)),
+        Span::styled(app.fmt_bnt(block.reward), Style::default().fg(theme.primary)),
     ]);
 
     // row 2: difficulty + mined time ago
     let row2 = Line::from(vec![
-        Span::styled(" Diff ", Style::default().fg(DIM)),
+        Span::styled(" Diff ", Style::default().fg(theme.dim)),
         Span::styled(format!("{:<6}", block.difficulty), Style::default().fg(Color::White)),
-        Span::styled("Mined ", Style::default().fg(DIM)),
+        Span::styled("Mined ", Style::default().fg(theme.dim)),
         Span::styled(format_time_ago(block.timestamp), Style::default().fg(Color::White)),
     ]);
 
-    // row 3: block time bar
-    let row3 = if let Some(secs) = block_time_secs {
-        let ratio = secs as f32 / 300.0;
+    // block time bar
+    let time_row = if let Some(secs) = block_time_secs {
+        let ratio = app.block_time_ratio(secs as f32);
         let time_color = if ratio < 0.5 {
             Color::Rgb(0, 255, 255)
         } else if ratio < 0.8 {
-            GREEN
+            theme.primary
         } else if ratio < 1.2 {
             Color::Rgb(170, 255, 0)
         } else if ratio < 2.0 {
-            Color::Yellow
+            theme.warning
         } else {
             Color::Rgb(255, 80, 80)
         };
@@ -223,32 +435,67 @@ fn render_block_info(frame: &mut Frame, app: &App, area: Rect) {
         };
 
         Line::from(vec![
-            Span::styled(label, Style::default().fg(DIM)),
+            Span::styled(label, Style::default().fg(theme.dim)),
             Span::styled(bar, Style::default().fg(time_color)),
             Span::styled(time_str, Style::default().fg(Color::White)),
         ])
+    } else if app.prune_height.is_some() {
+        Line::from(Span::styled(" Earlier blocks pruned", Style::default().fg(theme.dim)))
     } else {
-        Line::from(Span::styled(" Genesis block", Style::default().fg(DIM)))
+        Line::from(Span::styled(" Genesis block", Style::default().fg(theme.dim)))
     };
 
-    frame.render_widget(
-        Paragraph::new(vec![header, sep, row1, row2, Line::from(""), row3]),
-        area,
-    );
+    // row 3: hash, cut to fit; `h` copies it in full
+    let hash_w = w.saturating_sub(7);
+    let hash = if block.hash.len() > hash_w {
+        format!("{}…", &block.hash[..hash_w.saturating_sub(1)])
+    } else {
+        block.hash.clone()
+    };
+    let hash_row = Line::from(vec![
+        Span::styled(" Hash ", Style::default().fg(theme.dim)),
+        Span::styled(hash, Style::default().fg(Color::White)),
+    ]);
+
+    let mut lines = vec![header, sep, row1, row2, hash_row];
+
+    if app.info_density == InfoDensity::Verbose {
+        let fees: u64 = block.transactions.iter().map(|tx| tx.fee).sum();
+        lines.push(Line::from(vec![
+            Span::styled(" Conf ", Style::default().fg(theme.dim)),
+            Span::styled(format!("{:<6}", block.confirmations), Style::default().fg(Color::White)),
+            Span::styled("Fees ", Style::default().fg(theme.dim)),
+            Span::styled(app.fmt_bnt(fees), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(" Subsidy ", Style::default().fg(theme.dim)),
+            Span::styled(
+                app.fmt_bnt(block.reward.saturating_sub(fees)),
+                Style::default().fg(theme.primary),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(time_row);
+    lines
 }
 
-fn render_separator(frame: &mut Frame, area: Rect) {
+fn render_separator(frame: &mut Frame, area: Rect, theme: Theme) {
+    if area.width == 0 {
+        return;
+    }
     let buf = frame.buffer_mut();
-    let style = Style::default().fg(GREEN);
+    let style = Style::default().fg(theme.primary);
     for y in 0..area.height {
         buf[(area.x, area.y + y)].set_char('│').set_style(style);
     }
 }
 
-fn render_hrule(frame: &mut Frame, area: Rect) {
+fn render_hrule(frame: &mut Frame, area: Rect, theme: Theme) {
     let rule: String = "─".repeat(area.width as usize);
     frame.render_widget(
-        Paragraph::new(Span::styled(rule, Style::default().fg(GREEN))),
+        Paragraph::new(Span::styled(rule, Style::default().fg(theme.primary))),
         area,
     );
 }
@@ -256,6 +503,7 @@ fn render_hrule(frame: &mut Frame, area: Rect) {
 // ── Block grid: row gutter with heights, single-row color bar cells ──
 
 fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
     if area.width < 10 || area.height == 0 {
         return;
     }
@@ -268,7 +516,10 @@ fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // dynamic gutter width based on max block height
     let max_height = app.chain_blocks.last().map_or(0, |b| b.height);
-    let gutter_digits = format!("{}", max_height).len();
+    let mut gutter_digits = format!("{}", max_height).len();
+    if app.grid_timestamps {
+        gutter_digits = gutter_digits.max(5); // HH:MM
+    }
     let gutter_w = (gutter_digits as u16) + 1; // digits + 1 space
 
     // grid area: after gutter, with 1-char right margin for scrollbar
@@ -281,7 +532,9 @@ fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
     }
     app.blocks_per_row = blocks_per_row;
 
-    let total_rows = (total_blocks + blocks_per_row - 1) / blocks_per_row;
+    let block_rows = total_blocks.div_ceil(blocks_per_row);
+    // one extra row for the "pruned" band below the oldest available block
+    let total_rows = block_rows + usize::from(app.prune_height.is_some());
     let row_stride: u16 = 2; // 1 block row + 1 gap row
     let visible_rows = (area.height as usize + 1) / row_stride as usize;
 
@@ -304,6 +557,10 @@ fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
         .unwrap_or(1)
         .max(1);
 
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
     for vis_row in 0..visible_rows {
         let abs_row = app.grid_scroll_offset + vis_row;
         if abs_row >= total_rows {
@@ -311,20 +568,37 @@ fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
         }
 
         let py = area.y + (vis_row as u16) * row_stride;
+        if py >= area.bottom() {
+            break;
+        }
+
+        if abs_row >= block_rows {
+            if let Some(prune) = app.prune_height {
+                render_pruned_band(buf, grid_x, py, grid_w, prune, theme);
+            }
+            continue;
+        }
 
         // ── row gutter: height of the newest block in this row ──
         let first_grid_pos = abs_row * blocks_per_row;
         let first_block_idx = total_blocks - 1 - first_grid_pos;
-        let row_height = app.chain_blocks[first_block_idx].height;
-        let label = format!("{:>w$}", row_height, w = gutter_digits);
+        let row_block = &app.chain_blocks[first_block_idx];
+        let label = if app.grid_timestamps {
+            let secs_of_day = row_block.timestamp % 86_400;
+            let clock = format!("{:02}:{:02}", secs_of_day / 3600, secs_of_day % 3600 / 60);
+            format!("{:>w$}", clock, w = gutter_digits)
+        } else {
+            format!("{:>w$}", row_block.height, w = gutter_digits)
+        };
 
         let label_style = if abs_row == selected_row {
-            Style::default().fg(GREEN)
+            Style::default().fg(theme.primary)
         } else {
-            Style::default().fg(DIM)
+            Style::default().fg(theme.dim)
         };
 
-        for (i, ch) in label.chars().enumerate() {
+        // the gutter never runs into the cells or past the panel
+        for (i, ch) in label.chars().take(gutter_w.min(area.width) as usize).enumerate() {
             buf[(area.x + i as u16, py)].set_char(ch).set_style(label_style);
         }
 
@@ -338,13 +612,16 @@ fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
             let block_idx = total_blocks - 1 - grid_pos;
             let px = grid_x + (col as u16) * CELL_W;
 
-            if px + BLOCK_W > grid_x + grid_w || py >= area.y + area.height {
+            if px + BLOCK_W > grid_x + grid_w || px + BLOCK_W > area.right() {
                 continue;
             }
 
+            // a held new block blinks until the hold ends
+            let blink = app.held_block.is_some_and(|(h, _)| h == app.chain_blocks[block_idx].height)
+                && (app.tick_count / 8).is_multiple_of(2);
             if block_idx == app.selected {
                 // selected = bright hole, block is shown as spinning cube
-                let hole_style = Style::default().fg(GREEN);
+                let hole_style = Style::default().fg(if blink { theme.warning } else { theme.primary });
                 for dx in 0..BLOCK_W {
                     buf[(px + dx, py)].set_char('░').set_style(hole_style);
                 }
@@ -355,9 +632,37 @@ fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
                 let r = (255.0 - 85.0 * t) as u8;
                 let g = 255u8;
                 let b_val = (255.0 - 255.0 * t) as u8;
-                let fill_style = Style::default().fg(Color::Rgb(r, g, b_val));
-                for dx in 0..BLOCK_W {
-                    buf[(px + dx, py)].set_char('█').set_style(fill_style);
+                let fill_style = if blink {
+                    Style::default().fg(theme.warning)
+                } else if app.mined_by_us.contains(&block.height) {
+                    Style::default().fg(theme.accent)
+                } else {
+                    Style::default().fg(Color::Rgb(r, g, b_val))
+                };
+                if app.show_only_mine && !app.mined_by_us.contains(&block.height) {
+                    for dx in 0..BLOCK_W {
+                        buf[(px + dx, py)].set_char('·').set_style(Style::default().fg(theme.dim));
+                    }
+                } else if app.grid_timestamps || app.accessible {
+                    // accessible mode spells out the tx count instead of a color
+                    let glyph = if app.grid_timestamps {
+                        age_glyph(now.saturating_sub(block.timestamp))
+                    } else {
+                        format!("{:>2}", block.tx_count.min(99))
+                    };
+                    for (dx, ch) in glyph.chars().enumerate() {
+                        buf[(px + dx as u16, py)].set_char(ch).set_style(fill_style);
+                    }
+                } else {
+                    let ch = match app.grid_cell_style {
+                        GridCellStyle::Flat => '█',
+                        GridCellStyle::Bars => {
+                            BAR_GLYPHS[((t * 7.0).ceil() as usize).min(BAR_GLYPHS.len() - 1)]
+                        }
+                    };
+                    for dx in 0..BLOCK_W {
+                        buf[(px + dx, py)].set_char(ch).set_style(fill_style);
+                    }
                 }
             }
         }
@@ -367,26 +672,58 @@ fn render_block_grid(frame: &mut Frame, app: &mut App, area: Rect) {
     if total_rows > visible_rows {
         render_scrollbar(
             buf,
-            area.x + area.width - 1,
-            area.y,
-            area.height as usize,
+            area,
             app.grid_scroll_offset,
             total_rows,
             visible_rows,
+            theme,
         );
     }
 }
 
+/// Two-char block age for the timestamp grid: a leading digit (or `+` past
+/// 9) and a unit, e.g. `0m`, `7m`, `3h`, `+d`.
+fn age_glyph(secs: u64) -> String {
+    let (n, unit) = if secs < 3_600 {
+        (secs / 60, 'm')
+    } else if secs < 86_400 {
+        (secs / 3_600, 'h')
+    } else {
+        (secs / 86_400, 'd')
+    };
+    if n > 9 {
+        format!("+{}", unit)
+    } else {
+        format!("{}{}", n, unit)
+    }
+}
+
+fn render_pruned_band(buf: &mut Buffer, x: u16, y: u16, width: u16, prune_height: u64, theme: Theme) {
+    let style = Style::default().fg(theme.dim);
+    let note = format!(" pruned below #{} ", prune_height);
+    let note_start = 2;
+    for dx in 0..width {
+        let i = dx.saturating_sub(note_start) as usize;
+        let ch = if dx >= note_start && i < note.len() {
+            note.as_bytes()[i] as char
+        } else {
+            '╱'
+        };
+        buf[(x + dx, y)].set_char(ch).set_style(style);
+    }
+}
+
+/// Scrollbar down the rightmost column of `area`.
 fn render_scrollbar(
     buf: &mut Buffer,
-    x: u16,
-    y: u16,
-    track_h: usize,
+    area: Rect,
     offset: usize,
     total: usize,
     visible: usize,
+    theme: Theme,
 ) {
-    if track_h == 0 || total <= visible {
+    let (x, y, track_h) = (area.x + area.width.saturating_sub(1), area.y, area.height as usize);
+    if area.width == 0 || track_h == 0 || total <= visible {
         return;
     }
 
@@ -395,8 +732,8 @@ fn render_scrollbar(
     let thumb_start =
         ((offset as f32 / max_offset as f32) * (track_h - thumb_h) as f32) as usize;
 
-    let track_style = Style::default().fg(DIM);
-    let thumb_style = Style::default().fg(GREEN);
+    let track_style = Style::default().fg(theme.dim);
+    let thumb_style = Style::default().fg(theme.primary);
 
     for i in 0..track_h {
         let (ch, style) = if i >= thumb_start && i < thumb_start + thumb_h {
@@ -410,7 +747,17 @@ fn render_scrollbar(
 
 // ── Progress bar ──
 
+/// `300` → "5m", `90` → "1m30s", `45` → "45s".
+fn short_duration(secs: u64) -> String {
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{}s", s),
+        (m, 0) => format!("{}m", m),
+        (m, s) => format!("{}m{}s", m, s),
+    }
+}
+
 fn render_progress_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme;
     let w = area.width as usize;
     if w <= 20 {
         return;
@@ -424,31 +771,34 @@ fn render_progress_bar(frame: &mut Frame, app: &App, area: Rect) {
     let elapsed = now.saturating_sub(last_ts) as f32;
     let is_found = app.block_found_display > 0.0;
 
-    let ratio = (elapsed / 300.0).min(2.0);
+    let ratio = app.block_time_ratio(elapsed).min(2.0);
+    let progress = app.next_block_progress(elapsed);
     let label = " Next block ";
+    let target = short_duration(app.target_block_time_secs);
     let time_label = if elapsed < 60.0 {
-        format!(" {:.0}s / 5m ", elapsed)
+        format!(" {:.0}s / {} ", elapsed, target)
     } else {
         format!(
-            " {}m {:.0}s / 5m ",
+            " {}m {:.0}s / {} ",
             elapsed as u64 / 60,
-            elapsed % 60.0
+            elapsed % 60.0,
+            target
         )
     };
 
     let overhead = label.len() + time_label.len();
     let usable = w.saturating_sub(overhead);
-    let filled = ((ratio / 2.0) * usable as f32) as usize;
+    let filled = (progress * usable as f32) as usize;
     let target_pos = usable / 2;
 
     let bar_color = if is_found {
         Color::Rgb(255, 255, 100)
     } else if ratio < 0.8 {
-        Color::Rgb(0, 200, 255)
+        theme.accent
     } else if ratio < 1.2 {
-        GREEN
+        theme.primary
     } else {
-        Color::Yellow
+        theme.warning
     };
 
     let bar: String = (0..usable)
@@ -464,7 +814,7 @@ fn render_progress_bar(frame: &mut Frame, app: &App, area: Rect) {
         .collect();
 
     let mut spans = vec![
-        Span::styled(label, Style::default().fg(DIM)),
+        Span::styled(label, Style::default().fg(theme.dim)),
         Span::styled(bar, Style::default().fg(bar_color)),
         Span::styled(time_label, Style::default().fg(Color::White)),
     ];
@@ -4,64 +4,132 @@ use ratatui::{
     style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline, Wrap},
 };
 
-use crate::app::App;
-use crate::types::{format_bnt, format_time_ago};
-use super::{GREEN, DIM, PLASMA_CHARS};
+use crate::app::{App, ChartMetric, Congestion, PlasmaStyle, PollSource};
+use crate::types::{expected_blocks, format_fiat, format_time_ago};
+use super::{PLASMA_CHARS, fee_color, fee_legend, tx_fee_color};
 
-pub fn render(frame: &mut Frame, app: &mut App, title_area: Rect, content_area: Rect) {
-    // title
-    let title = Paragraph::new("Blocknet Dashboard")
-        .block(Block::default().title(" Dashboard ").borders(Borders::ALL))
-        .style(Style::new().fg(GREEN))
-        .alignment(Alignment::Center);
-    frame.render_widget(title, title_area);
+// below this width the panels stack in one scrollable column
+const STACK_BELOW_WIDTH: u16 = 80;
+// from this width the panels sit side by side in one row
+const ROW_FROM_WIDTH: u16 = 200;
+// height each panel gets when stacked
+const STACKED_PANEL_HEIGHT: u16 = 14;
+// a block-to-block difficulty change above this percent is marked on the
+// chart as a retarget
+const RETARGET_MARK_PCT: f64 = 2.0;
 
+pub fn render(frame: &mut Frame, app: &mut App, title_area: Rect, content_area: Rect) {
+    let theme = app.theme;
     // dashboard: panels + recent blocks ticker
     let dashboard = Layout::vertical([
         Constraint::Min(1),
         Constraint::Length(3),
     ])
     .split(content_area);
+    let panels_area = dashboard[0];
+
+    // 1 column (scrolling by whole panels) on narrow terminals, 1x4 on very
+    // wide ones, 2x2 otherwise
+    let panels: Vec<Rect> = if panels_area.width < STACK_BELOW_WIDTH {
+        let fit = (panels_area.height / STACKED_PANEL_HEIGHT).clamp(1, 4) as usize;
+        app.dashboard_max_scroll = 4 - fit;
+        app.dashboard_scroll = app.dashboard_scroll.min(app.dashboard_max_scroll);
+        let mut slots = vec![Rect::default(); 4];
+        let rows = Layout::vertical(vec![Constraint::Ratio(1, fit as u32); fit]).split(panels_area);
+        slots[app.dashboard_scroll..app.dashboard_scroll + fit].copy_from_slice(&rows);
+        slots
+    } else if panels_area.width >= ROW_FROM_WIDTH {
+        app.dashboard_max_scroll = 0;
+        Layout::horizontal([Constraint::Ratio(1, 4); 4])
+            .split(panels_area)
+            .to_vec()
+    } else {
+        app.dashboard_max_scroll = 0;
+        let rows = Layout::vertical([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ])
+        .split(panels_area);
+        let halves = [Constraint::Percentage(50), Constraint::Percentage(50)];
+        let top_cols = Layout::horizontal(halves).split(rows[0]);
+        let bot_cols = Layout::horizontal(halves).split(rows[1]);
+        vec![top_cols[0], top_cols[1], bot_cols[0], bot_cols[1]]
+    };
 
-    // 2x2 grid
-    let rows = Layout::vertical([
-        Constraint::Percentage(50),
-        Constraint::Percentage(50),
-    ])
-    .split(dashboard[0]);
+    // title
+    let heading = if app.dashboard_max_scroll > 0 {
+        format!(
+            "Blocknet Dashboard  [j/k] panels {}–{} of 4",
+            app.dashboard_scroll + 1,
+            app.dashboard_scroll + 4 - app.dashboard_max_scroll
+        )
+    } else {
+        "Blocknet Dashboard".to_string()
+    };
+    let title = Paragraph::new(heading)
+        .block(Block::default().title(" Dashboard ").borders(Borders::ALL))
+        .style(Style::new().fg(theme.primary))
+        .alignment(Alignment::Center);
+    frame.render_widget(title, title_area);
 
-    let top_cols = Layout::horizontal([
-        Constraint::Percentage(50),
-        Constraint::Percentage(50),
-    ])
-    .split(rows[0]);
+    // scrolled-out panels get an empty rect
+    if !panels[0].is_empty() {
+        render_chain_panel(frame, app, panels[0]);
+    }
+    if !panels[1].is_empty() {
+        render_wallet_panel(frame, app, panels[1]);
+    }
+    if !panels[2].is_empty() {
+        render_mempool_panel(frame, app, panels[2]);
+    }
+    if !panels[3].is_empty() {
+        render_mining_panel(frame, app, panels[3]);
+    }
+    render_recent_ticker(frame, app, dashboard[1]);
+}
 
-    let bot_cols = Layout::horizontal([
-        Constraint::Percentage(50),
-        Constraint::Percentage(50),
-    ])
-    .split(rows[1]);
+/// Dim "updated Ns ago" for a panel's bottom-right corner; red once a poll
+/// has been missed.
+fn updated_label(app: &App, last_tick: Option<u64>, interval: u64) -> Line<'static> {
+    let theme = app.theme;
+    let Some((secs, stale)) = app.staleness(last_tick, interval) else {
+        return Line::default();
+    };
+    let color = if stale { Color::Red } else { theme.dim };
+    Line::from(Span::styled(format!(" updated {}s ago ", secs), Style::default().fg(color)))
+        .right_aligned()
+}
 
-    render_chain_panel(frame, app, top_cols[0]);
-    render_wallet_panel(frame, app, top_cols[1]);
-    render_mempool_panel(frame, app, bot_cols[0]);
-    render_mining_panel(frame, app, bot_cols[1]);
-    render_recent_ticker(frame, app, dashboard[1]);
+/// Placeholder for a panel with nothing to show yet: why its last poll
+/// failed if it did, else `text`.
+fn waiting(app: &App, source: PollSource, text: &'static str) -> Paragraph<'static> {
+    match app.poll_error(source) {
+        Some(e) => Paragraph::new(format!(" {}", e))
+            .style(Style::new().fg(Color::Red))
+            .wrap(Wrap { trim: false }),
+        None => Paragraph::new(text).style(Style::new().fg(app.theme.dim)),
+    }
 }
 
 fn render_chain_panel(frame: &mut Frame, app: &App, area: Rect) {
-    let chain_border = Block::default().title(" Chain ").borders(Borders::ALL);
+    let theme = app.theme;
+    let chain_border = Block::default()
+        .title(" Chain ")
+        .title_bottom(updated_label(app, app.last_status_tick, app.status_poll_ticks))
+        .borders(Borders::ALL);
     let chain_inner = chain_border.inner(area);
-    frame.render_widget(chain_border.style(Style::new().fg(GREEN)), area);
+    frame.render_widget(chain_border.style(Style::new().fg(theme.primary)), area);
 
+    let syncing = app.status.as_ref().is_some_and(|s| s.syncing);
     let chain_parts = Layout::vertical([
         Constraint::Length(5),
+        Constraint::Length(syncing as u16), // sync rate
         Constraint::Length(1), // spacer
-        Constraint::Length(1), // diff label + lo/avg/hi
-        Constraint::Min(1),    // sparkline
+        Constraint::Length(1), // metric label + lo/avg/hi
+        Constraint::Min(1),    // chart
     ])
     .split(chain_inner);
 
@@ -69,20 +137,20 @@ fn render_chain_panel(frame: &mut Frame, app: &App, area: Rect) {
         let mut lines = vec![
             Line::from(""),
             Line::from(vec![
-                Span::styled("  Height: ", Style::default().fg(DIM)),
+                Span::styled("  Height: ", Style::default().fg(theme.dim)),
                 Span::styled(
                     format!("{}", stats.chain_height),
-                    Style::default().fg(GREEN).add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("  Peers:  ", Style::default().fg(DIM)),
+                Span::styled("  Peers:  ", Style::default().fg(theme.dim)),
                 Span::styled(format!("{}", stats.peers), Style::default().fg(Color::White)),
             ]),
         ];
         if stats.syncing {
             lines.push(Line::from(vec![
-                Span::styled("  Sync:   ", Style::default().fg(DIM)),
+                Span::styled("  Sync:   ", Style::default().fg(theme.dim)),
                 Span::styled(
                     format!(
                         "{}/{} ({})",
@@ -90,42 +158,49 @@ fn render_chain_panel(frame: &mut Frame, app: &App, area: Rect) {
                         stats.sync_target,
                         stats.sync_percent.as_deref().unwrap_or("0%")
                     ),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme.warning),
                 ),
             ]));
         } else {
             lines.push(Line::from(vec![
-                Span::styled("  Sync:   ", Style::default().fg(DIM)),
-                Span::styled("synced", Style::default().fg(GREEN)),
+                Span::styled("  Sync:   ", Style::default().fg(theme.dim)),
+                Span::styled("synced", Style::default().fg(theme.primary)),
+            ]));
+        }
+        if let Some(ref version) = app.daemon_version {
+            lines.push(Line::from(vec![
+                Span::styled("  Daemon: ", Style::default().fg(theme.dim)),
+                Span::styled(version.clone(), Style::default().fg(Color::White)),
             ]));
         }
         frame.render_widget(Paragraph::new(lines), chain_parts[0]);
+        if syncing {
+            render_sync_rate(frame, app, chain_parts[1]);
+        }
     } else {
-        frame.render_widget(
-            Paragraph::new(" Waiting for node...").style(Style::new().fg(DIM)),
-            chain_parts[0],
-        );
+        frame.render_widget(waiting(app, PollSource::Status, " Waiting for node..."), chain_parts[0]);
     }
 
-    // difficulty line chart (braille)
-    let difficulties: Vec<u64> = app.chain_blocks.iter().map(|b| b.difficulty).collect();
-    if !difficulties.is_empty() {
-        let chart_w = chain_parts[3].width as usize;
-        let slice = &difficulties[difficulties.len().saturating_sub(chart_w)..];
+    // line chart (braille) of the metric picked with `C`
+    let metric = app.chart_metric;
+    let series = metric.series(&app.chain_blocks);
+    let chart_w = chain_parts[4].width as usize;
+    if !series.is_empty() && chart_w > 0 {
+        let slice = &series[series.len().saturating_sub(chart_w)..];
         let lo = slice.iter().copied().min().unwrap_or(0);
         let hi = slice.iter().copied().max().unwrap_or(0);
         let avg = slice.iter().copied().sum::<u64>() / slice.len() as u64;
 
         let stats_line = Line::from(vec![
-            Span::styled("  diff ", Style::default().fg(DIM)),
-            Span::styled("lo ", Style::default().fg(DIM)),
-            Span::styled(format!("{}", lo), Style::default().fg(Color::White)),
-            Span::styled("  avg ", Style::default().fg(DIM)),
-            Span::styled(format!("{}", avg), Style::default().fg(Color::White)),
-            Span::styled("  hi ", Style::default().fg(DIM)),
-            Span::styled(format!("{}", hi), Style::default().fg(Color::White)),
+            Span::styled(format!("  {} ", metric.label()), Style::default().fg(theme.dim)),
+            Span::styled("lo ", Style::default().fg(theme.dim)),
+            Span::styled(metric.format(lo), Style::default().fg(Color::White)),
+            Span::styled("  avg ", Style::default().fg(theme.dim)),
+            Span::styled(metric.format(avg), Style::default().fg(Color::White)),
+            Span::styled("  hi ", Style::default().fg(theme.dim)),
+            Span::styled(metric.format(hi), Style::default().fg(Color::White)),
         ]);
-        frame.render_widget(Paragraph::new(stats_line), chain_parts[2]);
+        frame.render_widget(Paragraph::new(stats_line), chain_parts[3]);
 
         // convert to (f64, f64) for Chart
         let data: Vec<(f64, f64)> = slice
@@ -139,59 +214,216 @@ fn render_chain_panel(frame: &mut Frame, app: &App, area: Rect) {
         let y_lo = lo as f64 - margin;
         let y_hi = hi as f64 + margin;
 
-        let dataset = Dataset::default()
-            .marker(symbols::Marker::Braille)
-            .graph_type(GraphType::Line)
-            .style(Style::default().fg(GREEN))
-            .data(&data);
+        // dashed reference line at the average, drawn first so the series
+        // covers it where they meet (all of it when the series is flat)
+        let avg_line: Vec<(f64, f64)> = (0..slice.len() * 2)
+            .filter(|k| k % 4 < 2)
+            .map(|k| (k as f64 / 2.0, avg as f64))
+            .collect();
 
-        let chart = Chart::new(vec![dataset])
+        // difficulty retargets: points that moved more than
+        // RETARGET_MARK_PCT from the block before
+        let retargets: Vec<(f64, f64)> = if metric == ChartMetric::Difficulty {
+            slice
+                .windows(2)
+                .enumerate()
+                .filter(|(_, w)| {
+                    w[0] > 0 && w[0].abs_diff(w[1]) as f64 * 100.0 / w[0] as f64 > RETARGET_MARK_PCT
+                })
+                .map(|(i, w)| ((i + 1) as f64, w[1] as f64))
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let datasets = vec![
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(theme.dim))
+                .data(&avg_line),
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.primary))
+                .data(&data),
+            Dataset::default()
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(theme.warning))
+                .data(&retargets),
+        ];
+
+        let chart = Chart::new(datasets)
             .x_axis(Axis::default().bounds([0.0, (slice.len() - 1).max(1) as f64]))
             .y_axis(Axis::default().bounds([y_lo, y_hi]));
 
-        frame.render_widget(chart, chain_parts[3]);
+        if app.accessible {
+            let trend = match slice.last().cmp(&slice.first()) {
+                std::cmp::Ordering::Greater => "rising",
+                std::cmp::Ordering::Less => "falling",
+                std::cmp::Ordering::Equal => "flat",
+            };
+            let mut text = format!(
+                "  trend {} over {} blocks ({} -> {})",
+                trend,
+                slice.len(),
+                metric.format(slice[0]),
+                metric.format(slice[slice.len() - 1])
+            );
+            if metric == ChartMetric::Difficulty {
+                text.push_str(&format!(", retargets >{}%: {}", RETARGET_MARK_PCT, retargets.len()));
+            }
+            frame.render_widget(
+                Paragraph::new(text)
+                .style(Style::new().fg(Color::White)),
+                chain_parts[4],
+            );
+        } else {
+            frame.render_widget(chart, chain_parts[4]);
+        }
+    }
+}
+
+/// Sync speed under the chain stats: blocks per second and time left, with
+/// a sparkline of the rate in the rest of the row.
+fn render_sync_rate(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme;
+    let text = match (app.sync_rate(), app.sync_eta()) {
+        (Some(rate), Some(eta)) => format!("{:.1} blk/s · ETA {} ", rate, eta_label(eta)),
+        (Some(_), None) => "stalled ".to_string(),
+        (None, _) => "measuring... ".to_string(),
+    };
+    let label = Line::from(vec![
+        Span::styled("  Rate:   ", Style::default().fg(theme.dim)),
+        Span::styled(text, Style::default().fg(theme.warning)),
+    ]);
+    let label_w = (label.width() as u16).min(area.width);
+    frame.render_widget(Paragraph::new(label), area);
+
+    let spark_area = Rect { x: area.x + label_w, width: area.width - label_w, ..area };
+    if app.accessible || spark_area.is_empty() {
+        return;
+    }
+    let series = app.sync_rate_series();
+    let slice = &series[series.len().saturating_sub(spark_area.width as usize)..];
+    frame.render_widget(
+        Sparkline::default().data(slice).style(Style::default().fg(theme.warning)),
+        spark_area,
+    );
+}
+
+/// Sync time left: "45s", "12m", "3h05m", "2d4h".
+fn eta_label(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3_600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h{:02}m", s / 3_600, s % 3_600 / 60),
+        s => format!("{}d{}h", s / 86_400, s % 86_400 / 3_600),
     }
 }
 
 fn render_wallet_panel(frame: &mut Frame, app: &App, area: Rect) {
-    let wallet_border =
-        Block::default().title(" Wallet ").borders(Borders::ALL).style(Style::new().fg(GREEN));
+    let theme = app.theme;
+    let wallet_border = Block::default()
+        .title(" Wallet ")
+        .title_bottom(updated_label(app, app.last_balance_tick, app.data_poll_ticks))
+        .borders(Borders::ALL)
+        .style(Style::new().fg(theme.primary));
     let wallet_inner = wallet_border.inner(area);
     frame.render_widget(wallet_border, area);
 
     let wallet_parts = Layout::vertical([
-        Constraint::Length(5),
+        Constraint::Length(5 + u16::from(app.balance_mark.is_some())),
         Constraint::Min(1), // constellation
     ])
     .split(wallet_inner);
 
     if let Some(ref balance) = app.balance {
-        let lines = vec![
+        let mut lines = vec![
             Line::from(""),
             Line::from(vec![
-                Span::styled("  Spendable: ", Style::default().fg(DIM)),
+                Span::styled("  Spendable: ", Style::default().fg(theme.dim)),
                 Span::styled(
-                    format_bnt(balance.spendable),
-                    Style::default().fg(GREEN).add_modifier(Modifier::BOLD),
+                    app.fmt_bnt(balance.spendable),
+                    Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("  Pending:   ", Style::default().fg(DIM)),
-                Span::styled(format_bnt(balance.pending), Style::default().fg(Color::Yellow)),
+                Span::styled("  Pending:   ", Style::default().fg(theme.dim)),
+                Span::styled(app.fmt_bnt(balance.pending), Style::default().fg(theme.warning)),
             ]),
             Line::from(vec![
-                Span::styled("  Total:     ", Style::default().fg(DIM)),
-                Span::styled(format_bnt(balance.total), Style::default().fg(Color::White)),
+                Span::styled("  Total:     ", Style::default().fg(theme.dim)),
+                Span::styled(app.fmt_bnt(balance.total), Style::default().fg(Color::White)),
             ]),
         ];
+        if app.show_fiat
+            && let Some(rate) = app.fiat_rate
+            && let Some(total) = lines.last_mut()
+        {
+            total.spans.push(Span::styled(
+                format!("  {}", format_fiat(balance.total, rate, &app.config.fiat.symbol)),
+                Style::default().fg(theme.dim),
+            ));
+        }
+        // the wallet rescans after a restart/import; until it catches up the
+        // balance is as of an older height. One block of lag is just the
+        // balance poll running behind the status poll.
+        let node_height = app.status.as_ref().map_or(0, |s| s.chain_height);
+        if node_height > balance.chain_height + 1 {
+            let ratio = balance.chain_height as f64 / node_height as f64;
+            let filled = (ratio * 10.0) as usize;
+            lines.push(Line::from(vec![
+                Span::styled("  Scanning:  ", Style::default().fg(theme.dim)),
+                Span::styled(
+                    format!("{}{} ", "▰".repeat(filled), "▱".repeat(10 - filled)),
+                    Style::default().fg(theme.warning),
+                ),
+                Span::styled(
+                    format!("{}/{}", balance.chain_height, node_height),
+                    Style::default().fg(theme.dim),
+                ),
+            ]));
+        }
+        if let Some((marked_total, marked_height)) = app.balance_mark {
+            let sign = if balance.total >= marked_total { '+' } else { '-' };
+            let blocks = node_height as i64 - marked_height as i64;
+            lines.push(Line::from(vec![
+                Span::styled("  Mark:      ", Style::default().fg(theme.dim)),
+                Span::styled(
+                    format!("{}{}", sign, app.fmt_bnt(balance.total.abs_diff(marked_total))),
+                    Style::default().fg(if sign == '+' { theme.primary } else { Color::Red }),
+                ),
+                Span::styled(format!(", {:+} blocks", blocks), Style::default().fg(theme.dim)),
+            ]));
+        }
         frame.render_widget(Paragraph::new(lines), wallet_parts[0]);
 
-        render_constellation(frame, balance.outputs_unspent, app.tick_count, wallet_parts[1]);
-    } else {
+        if app.accessible {
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "  Outputs:   {} unspent of {}",
+                    balance.outputs_unspent, balance.outputs_total
+                ))
+                .style(Style::new().fg(Color::White)),
+                wallet_parts[1],
+            );
+        } else {
+            render_constellation(frame, balance.outputs_unspent, app.animation_ticks, wallet_parts[1]);
+        }
+    } else if app.wallet_missing {
+        let text = if app.readonly {
+            " No wallet on this node."
+        } else {
+            " No wallet on this node. Press [r] to create one."
+        };
         frame.render_widget(
-            Paragraph::new(" Waiting for data...").style(Style::new().fg(DIM)),
+            Paragraph::new(text).style(Style::new().fg(theme.warning)),
             wallet_parts[0],
         );
+    } else {
+        frame.render_widget(waiting(app, PollSource::Balance, " Waiting for data..."), wallet_parts[0]);
     }
 }
 
@@ -209,7 +441,7 @@ fn render_constellation(frame: &mut Frame, utxo_count: u32, tick: u64, area: Rec
 
     // independent irrationals for quasi-random 2D scatter
     const PHI: f64 = 1.618033988749895;   // golden ratio
-    const SQRT2: f64 = 1.414213562373095;  // sqrt(2), independent from PHI
+    const SQRT2: f64 = std::f64::consts::SQRT_2; // independent from PHI
 
     let mut lines: Vec<Line> = Vec::with_capacity(h);
 
@@ -269,42 +501,68 @@ fn render_constellation(frame: &mut Frame, utxo_count: u32, tick: u64, area: Rec
 }
 
 fn render_mempool_panel(frame: &mut Frame, app: &App, area: Rect) {
-    let mempool_border =
-        Block::default().title(" Mempool ").borders(Borders::ALL).style(Style::new().fg(GREEN));
+    let theme = app.theme;
+    let border_color = match app.mempool_congestion() {
+        Congestion::Normal => theme.primary,
+        Congestion::Elevated => theme.warning,
+        Congestion::Congested => Color::Red,
+    };
+    let mempool_border = Block::default()
+        .title(" Mempool ")
+        .title_bottom(updated_label(app, app.last_mempool_tick, app.data_poll_ticks))
+        .borders(Borders::ALL)
+        .style(Style::new().fg(theme.primary))
+        .border_style(Style::new().fg(border_color));
+    let mempool_border = if app.show_mempool_txs {
+        mempool_border.title_bottom(Line::from(" [X] sort · [/] filter · [x] totals ").left_aligned())
+    } else {
+        mempool_border
+    };
     let mempool_inner = mempool_border.inner(area);
     frame.render_widget(mempool_border, area);
 
-    if app.mempool_history.is_empty() {
-        let mempool_parts = Layout::vertical([Constraint::Min(1)]).split(mempool_inner);
+    if app.show_mempool_txs {
+        render_mempool_txs(frame, app, mempool_inner);
+        return;
+    }
+
+    // accessible mode keeps to the plain numbers
+    if app.mempool_history.is_empty() || app.accessible {
+        let mempool_parts = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(1), // fee legend
+        ])
+        .split(mempool_inner);
 
         if let Some(ref mempool) = app.mempool {
             let lines = vec![
                 Line::from(""),
                 Line::from(vec![
-                    Span::styled("  Transactions: ", Style::default().fg(DIM)),
+                    Span::styled("  Transactions: ", Style::default().fg(theme.dim)),
                     Span::styled(format!("{}", mempool.count), Style::default().fg(Color::White)),
                 ]),
                 Line::from(vec![
-                    Span::styled("  Size:         ", Style::default().fg(DIM)),
+                    Span::styled("  Size:         ", Style::default().fg(theme.dim)),
                     Span::styled(
                         format!("{} bytes", mempool.size_bytes),
                         Style::default().fg(Color::White),
                     ),
                 ]),
                 Line::from(vec![
-                    Span::styled("  Avg fee:      ", Style::default().fg(DIM)),
+                    Span::styled("  Avg fee:      ", Style::default().fg(theme.dim)),
                     Span::styled(
-                        format_bnt(mempool.avg_fee as u64),
-                        Style::default().fg(Color::White),
+                        app.fmt_bnt(mempool.avg_fee as u64),
+                        Style::default().fg(mempool.avg_fee_rate().map_or(theme.dim, fee_color)),
                     ),
                 ]),
             ];
             frame.render_widget(Paragraph::new(lines), mempool_parts[0]);
         } else {
-            frame.render_widget(
-                Paragraph::new(" Waiting for data...").style(Style::new().fg(DIM)),
-                mempool_parts[0],
-            );
+            frame.render_widget(waiting(app, PollSource::Mempool, " Waiting for data..."), mempool_parts[0]);
+        }
+        // the legend explains the colors before there's anything to color
+        if !app.accessible {
+            frame.render_widget(Paragraph::new(fee_legend(mempool_parts[1].width, theme)), mempool_parts[1]);
         }
     } else {
         // 3 stacked sparklines: txs, size, fee
@@ -315,6 +573,7 @@ fn render_mempool_panel(frame: &mut Frame, app: &App, area: Rect) {
             Constraint::Min(1),
             Constraint::Length(1),
             Constraint::Min(1),
+            Constraint::Length(1), // fee legend
         ])
         .split(mempool_inner);
 
@@ -326,10 +585,10 @@ fn render_mempool_panel(frame: &mut Frame, app: &App, area: Rect) {
         let tx_cur = tx_slice.last().copied().unwrap_or(0);
         frame.render_widget(
             Paragraph::new(Line::from(vec![
-                Span::styled("  txs ", Style::default().fg(DIM)),
+                Span::styled("  txs ", Style::default().fg(theme.dim)),
                 Span::styled(
                     format!("{}", tx_cur),
-                    Style::default().fg(Color::Rgb(0, 200, 255)),
+                    Style::default().fg(theme.accent),
                 ),
             ])),
             mempool_parts[0],
@@ -337,7 +596,7 @@ fn render_mempool_panel(frame: &mut Frame, app: &App, area: Rect) {
         frame.render_widget(
             Sparkline::default()
                 .data(tx_slice)
-                .style(Style::default().fg(Color::Rgb(0, 200, 255))),
+                .style(Style::default().fg(theme.accent)),
             mempool_parts[1],
         );
 
@@ -354,15 +613,15 @@ fn render_mempool_panel(frame: &mut Frame, app: &App, area: Rect) {
         };
         frame.render_widget(
             Paragraph::new(Line::from(vec![
-                Span::styled("  size ", Style::default().fg(DIM)),
-                Span::styled(size_str, Style::default().fg(Color::Yellow)),
+                Span::styled("  size ", Style::default().fg(theme.dim)),
+                Span::styled(size_str, Style::default().fg(theme.warning)),
             ])),
             mempool_parts[2],
         );
         frame.render_widget(
             Sparkline::default()
                 .data(size_slice)
-                .style(Style::default().fg(Color::Yellow)),
+                .style(Style::default().fg(theme.warning)),
             mempool_parts[3],
         );
 
@@ -370,30 +629,106 @@ fn render_mempool_panel(frame: &mut Frame, app: &App, area: Rect) {
         let fee_slice =
             &app.mempool_fee_history[app.mempool_fee_history.len().saturating_sub(mp_w)..];
         let fee_cur = fee_slice.last().copied().unwrap_or(0);
+        let fee_rate = app.mempool.as_ref().and_then(|m| m.avg_fee_rate());
+        let fee_style = Style::default().fg(fee_rate.map_or(theme.dim, fee_color));
         frame.render_widget(
             Paragraph::new(Line::from(vec![
-                Span::styled("  fee ", Style::default().fg(DIM)),
-                Span::styled(format_bnt(fee_cur), Style::default().fg(Color::Magenta)),
+                Span::styled("  fee ", Style::default().fg(theme.dim)),
+                Span::styled(app.fmt_bnt(fee_cur), fee_style),
             ])),
             mempool_parts[4],
         );
         frame.render_widget(
-            Sparkline::default()
-                .data(fee_slice)
-                .style(Style::default().fg(Color::Magenta)),
+            Sparkline::default().data(fee_slice).style(fee_style),
             mempool_parts[5],
         );
+        frame.render_widget(Paragraph::new(fee_legend(mempool_parts[6].width, theme)), mempool_parts[6]);
     }
 }
 
+/// Pending transactions in place of the Mempool panel's totals, sorted by
+/// fee and narrowed to the txid filter.
+fn render_mempool_txs(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme;
+    if area.height == 0 {
+        return;
+    }
+    let txs = match (&app.mempool_txs, &app.mempool_txs_error) {
+        (_, Some(err)) => {
+            frame.render_widget(
+                Paragraph::new(format!(" {}", err))
+                    .style(Style::new().fg(Color::Red))
+                    .wrap(Wrap { trim: false }),
+                area,
+            );
+            return;
+        }
+        (None, None) => {
+            frame.render_widget(Paragraph::new(" Loading…").style(Style::new().fg(theme.dim)), area);
+            return;
+        }
+        (Some(_), None) => app.filtered_mempool_txs(),
+    };
+
+    let order = if app.mempool_txs_ascending { "fee ↑" } else { "fee ↓" };
+    let mut header = vec![Span::styled(format!(" {} txs · {}", txs.len(), order), Style::default().fg(theme.dim))];
+    if !app.mempool_txs_filter.is_empty() {
+        header.push(Span::styled(" · txid ", Style::default().fg(theme.dim)));
+        header.push(Span::styled(format!("{}…", app.mempool_txs_filter), Style::default().fg(theme.warning)));
+    }
+    let mut lines = vec![Line::from(header)];
+    if txs.is_empty() {
+        let text = if app.mempool_txs_filter.is_empty() {
+            " Mempool is empty"
+        } else {
+            " No txid starts with that"
+        };
+        lines.push(Line::from(Span::styled(text, Style::default().fg(theme.dim))));
+    }
+
+    // txid takes what the fee and size columns leave
+    let fee_w = txs.iter().map(|tx| app.fmt_bnt(tx.fee).chars().count()).max().unwrap_or(0);
+    let size_w = 8;
+    let txid_w = (area.width as usize).saturating_sub(fee_w + size_w + 4).clamp(8, 64);
+    let rows = (area.height as usize).saturating_sub(1);
+    for (i, tx) in txs.iter().enumerate().take(rows) {
+        if i + 1 == rows && txs.len() > rows {
+            lines.push(Line::from(Span::styled(
+                format!(" +{} more...", txs.len() - i),
+                Style::default().fg(theme.dim),
+            )));
+            break;
+        }
+        let txid: String = if tx.txid.chars().count() > txid_w {
+            let head: String = tx.txid.chars().take(txid_w - 1).collect();
+            format!("{}…", head)
+        } else {
+            tx.txid.clone()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<w$} ", txid, w = txid_w), Style::default().fg(Color::White)),
+            Span::styled(
+                format!("{:>w$}", app.fmt_bnt(tx.fee), w = fee_w),
+                Style::default().fg(tx_fee_color(tx.fee, Some(tx.size), theme.dim)),
+            ),
+            Span::styled(format!("{:>w$}", format!("{} B", tx.size), w = size_w + 1), Style::default().fg(theme.dim)),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
 fn render_mining_panel(frame: &mut Frame, app: &App, area: Rect) {
-    let mining_border =
-        Block::default().title(" Mining ").borders(Borders::ALL).style(Style::new().fg(GREEN));
+    let theme = app.theme;
+    let mining_border = Block::default()
+        .title(" Mining ")
+        .title_bottom(updated_label(app, app.last_mining_tick, app.data_poll_ticks))
+        .borders(Borders::ALL)
+        .style(Style::new().fg(theme.primary));
     let mining_inner = mining_border.inner(area);
     frame.render_widget(mining_border, area);
 
     let mining_parts = Layout::vertical([
-        Constraint::Length(7),
+        Constraint::Length(7 + !app.mined_by_us.is_empty() as u16),
         Constraint::Min(1),
     ])
     .split(mining_inner);
@@ -402,50 +737,71 @@ fn render_mining_panel(frame: &mut Frame, app: &App, area: Rect) {
         let status_line = if mining.running {
             Line::from(vec![
                 Span::styled("  ", Style::default()),
-                Span::styled("●", Style::default().fg(GREEN)),
+                Span::styled("●", Style::default().fg(theme.primary)),
                 Span::styled(
                     format!(" Mining  ({} threads)", mining.threads),
-                    Style::default().fg(GREEN),
+                    Style::default().fg(theme.primary),
                 ),
             ])
         } else {
             Line::from(vec![
                 Span::styled("  ", Style::default()),
-                Span::styled("○", Style::default().fg(DIM)),
-                Span::styled(format!(" Idle ({} threads)", mining.threads), Style::default().fg(DIM)),
+                Span::styled("○", Style::default().fg(theme.dim)),
+                Span::styled(format!(" Idle ({} threads)", mining.threads), Style::default().fg(theme.dim)),
             ])
         };
         let mut lines = vec![Line::from(""), status_line];
         if mining.running {
             lines.push(Line::from(vec![
-                Span::styled("  Hashrate:  ", Style::default().fg(DIM)),
+                Span::styled("  Hashrate:  ", Style::default().fg(theme.dim)),
                 Span::styled(
                     format!("{:.2} H/s", mining.hashrate),
                     Style::default().fg(Color::White),
                 ),
             ]));
             lines.push(Line::from(vec![
-                Span::styled("  Hashes:    ", Style::default().fg(DIM)),
+                Span::styled("  Hashes:    ", Style::default().fg(theme.dim)),
                 Span::styled(format!("{}", mining.hash_count), Style::default().fg(Color::White)),
             ]));
         }
         lines.push(Line::from(vec![
-            Span::styled("  Found:     ", Style::default().fg(DIM)),
+            Span::styled("  Found:     ", Style::default().fg(theme.dim)),
             Span::styled(
                 format!("{} blocks", mining.blocks_found),
-                Style::default().fg(if mining.blocks_found > 0 { GREEN } else { Color::White }),
+                Style::default().fg(if mining.blocks_found > 0 { theme.primary } else { Color::White }),
             ),
         ]));
+        if !app.mined_by_us.is_empty() {
+            let recent: Vec<String> = app.mined_by_us.iter().rev().take(4).map(|h| format!("#{}", h)).collect();
+            lines.push(Line::from(vec![
+                Span::styled("  Mined:     ", Style::default().fg(theme.dim)),
+                Span::styled(recent.join(" "), Style::default().fg(theme.accent)),
+            ]));
+        }
+        let difficulty = app.chain_blocks.last().map_or(0, |b| b.difficulty);
+        if mining.running {
+            let per_day = expected_blocks(mining.hashrate * 86_400.0, difficulty);
+            lines.push(Line::from(vec![
+                Span::styled("  Expected:  ", Style::default().fg(theme.dim)),
+                Span::styled(format!("{:.2} blocks/day", per_day), Style::default().fg(Color::White)),
+            ]));
+        }
+        if let Some(luck) = app.mining_luck() {
+            let luck_color = if luck >= 100.0 { theme.primary } else { theme.warning };
+            lines.push(Line::from(vec![
+                Span::styled("  Luck:      ", Style::default().fg(theme.dim)),
+                Span::styled(format!("{:.0}%", luck), Style::default().fg(luck_color)),
+            ]));
+        }
         frame.render_widget(Paragraph::new(lines), mining_parts[0]);
     } else {
-        frame.render_widget(
-            Paragraph::new(" Waiting for data...").style(Style::new().fg(DIM)),
-            mining_parts[0],
-        );
+        frame.render_widget(waiting(app, PollSource::Mining, " Waiting for data..."), mining_parts[0]);
     }
 
     // plasma interference field
-    render_plasma(frame, app, mining_parts[1]);
+    if !app.accessible {
+        render_plasma(frame, app, mining_parts[1]);
+    }
 }
 
 fn render_plasma(frame: &mut Frame, app: &App, area: Rect) {
@@ -467,18 +823,32 @@ fn render_plasma(frame: &mut Frame, app: &App, area: Rect) {
         for col in 0..w {
             let x = col as f32;
 
-            // 4 overlapping wave functions
-            let v1 = (x * 0.15 + t * 1.3).sin();
-            let v2 = (y * 0.2 + t * 0.9).cos();
             let dx = x - cx;
             let dy = (y - cy) * 2.0;
             let dist = (dx * dx + dy * dy).sqrt();
-            let v3 = (dist * 0.12 - t * 1.6).sin();
-            let v4 = ((x * 0.07 + y * 0.13 + t * 0.7).sin()
-                + (x * 0.13 - y * 0.09 + t * 1.1).cos())
-                * 0.5;
 
-            let mut v = (v1 + v2 + v3 + v4) / 4.0;
+            let mut v = match app.plasma_style {
+                PlasmaStyle::Waves => {
+                    // 4 overlapping wave functions
+                    let v1 = (x * 0.15 + t * 1.3).sin();
+                    let v2 = (y * 0.2 + t * 0.9).cos();
+                    let v3 = (dist * 0.12 - t * 1.6).sin();
+                    let v4 = ((x * 0.07 + y * 0.13 + t * 0.7).sin()
+                        + (x * 0.13 - y * 0.09 + t * 1.1).cos())
+                        * 0.5;
+                    (v1 + v2 + v3 + v4) / 4.0
+                }
+                PlasmaStyle::Rings => {
+                    // rings expanding from the center, twisted by angle
+                    let angle = dy.atan2(dx);
+                    (dist * 0.35 - t * 2.0).sin() * 0.6 + (angle * 3.0 + t).sin() * 0.4
+                }
+                PlasmaStyle::Noise => {
+                    // two octaves of drifting value noise
+                    value_noise(x * 0.12 + t * 0.5, y * 0.25 - t * 0.3) * 0.7
+                        + value_noise(x * 0.3 - t * 0.8, y * 0.6 + t * 0.4) * 0.3
+                }
+            };
             v = v * 0.5 + 0.5;
 
             // shockwave
@@ -495,11 +865,11 @@ fn render_plasma(frame: &mut Frame, app: &App, area: Rect) {
 
             v *= intensity;
 
-            let ci = (v * 9.0).min(9.0).max(0.0) as usize;
+            let ci = (v * 9.0).clamp(0.0, 9.0) as usize;
             let ch = PLASMA_CHARS[ci];
 
             let hue = v * 0.8 + (dist * 0.01 + t * 0.3).sin() * 0.2;
-            let r = (hue * 170.0).min(170.0).max(0.0) as u8;
+            let r = (hue * 170.0).clamp(0.0, 170.0) as u8;
             let g = (v * 255.0).min(255.0) as u8;
             let b = ((1.0 - hue) * 40.0).max(0.0) as u8;
 
@@ -533,7 +903,26 @@ fn render_plasma(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(Paragraph::new(plasma_lines), area);
 }
 
+/// Smoothly interpolated lattice noise in -1..1.
+fn value_noise(x: f32, y: f32) -> f32 {
+    fn lattice(ix: i32, iy: i32) -> f32 {
+        let mut h = (ix as u32).wrapping_mul(0x27d4_eb2d) ^ (iy as u32).wrapping_mul(0x1656_67b1);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x85eb_ca6b);
+        h ^= h >> 13;
+        (h & 0xffff) as f32 / 32_767.5 - 1.0
+    }
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (sx, sy) = (fx * fx * (3.0 - 2.0 * fx), fy * fy * (3.0 - 2.0 * fy));
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let top = lattice(ix, iy) + (lattice(ix + 1, iy) - lattice(ix, iy)) * sx;
+    let bottom = lattice(ix, iy + 1) + (lattice(ix + 1, iy + 1) - lattice(ix, iy + 1)) * sx;
+    top + (bottom - top) * sy
+}
+
 fn render_recent_ticker(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme;
     let recent_text: String = app
         .chain_blocks
         .iter()
@@ -552,6 +941,6 @@ fn render_recent_ticker(frame: &mut Frame, app: &App, area: Rect) {
 
     let recent = Paragraph::new(Line::from(format!(" {}", recent_text)))
         .block(Block::default().title(" Recent Blocks ").borders(Borders::ALL))
-        .style(Style::new().fg(DIM));
+        .style(Style::new().fg(theme.dim));
     frame.render_widget(recent, area);
 }
@@ -0,0 +1,102 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::app::App;
+use crate::types::format_time_ago;
+
+// column widths; the address column takes whatever is left
+const WHEN_W: usize = 10;
+const TXID_W: usize = 14;
+
+pub fn render(frame: &mut Frame, app: &App, title_area: Rect, content_area: Rect) {
+    let theme = app.theme;
+    let full = Rect {
+        x: title_area.x,
+        y: title_area.y,
+        width: title_area.width.max(content_area.width),
+        height: title_area.height + content_area.height,
+    };
+
+    let title = match &app.tx_log_entries {
+        Ok(entries) if !entries.is_empty() => format!(" History · {} sent ", entries.len()),
+        _ => " History ".to_string(),
+    };
+    let border = Block::default()
+        .title(title)
+        .title_bottom(Line::from(" [j/k] select · [y/Y] copy txid/URL · [Enter] open ").right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.primary));
+    let inner = border.inner(full);
+    frame.render_widget(border, full);
+    if inner.height == 0 {
+        return;
+    }
+
+    let message = |text: String, color: Color| {
+        Paragraph::new(Line::from(Span::styled(format!(" {}", text), Style::default().fg(color))))
+    };
+    let entries = match &app.tx_log_entries {
+        Err(e) => {
+            frame.render_widget(message(format!("Can't read tx.log: {}", e), Color::Red), inner);
+            return;
+        }
+        Ok(entries) if entries.is_empty() => {
+            let text = if app.config.wallet.tx_log {
+                "No transactions yet"
+            } else {
+                "No transactions sent this session (tx.log is turned off)"
+            };
+            frame.render_widget(message(text.into(), theme.dim), inner);
+            return;
+        }
+        Ok(entries) => entries,
+    };
+
+    let amount_w = entries.iter().map(|e| app.fmt_bnt(e.amount).chars().count()).max().unwrap_or(0) + 2;
+    let addr_w = (inner.width as usize).saturating_sub(1 + WHEN_W + amount_w + TXID_W).max(8);
+    let cut = |text: &str, w: usize| {
+        if text.chars().count() < w {
+            format!("{:<w$}", text, w = w)
+        } else {
+            let head: String = text.chars().take(w.saturating_sub(2)).collect();
+            format!("{}… ", head)
+        }
+    };
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            " {}{:>aw$}  {}{}",
+            cut("When", WHEN_W),
+            "Amount",
+            cut("Txid", TXID_W),
+            "To",
+            aw = amount_w - 2
+        ),
+        Style::default().fg(theme.dim),
+    ))];
+
+    // keep the selected row on screen
+    let rows = (inner.height as usize).saturating_sub(1);
+    let start = (app.tx_log_selected + 1).saturating_sub(rows);
+    for (i, entry) in entries.iter().enumerate().skip(start).take(rows) {
+        let (text_style, dim_style) = if i == app.tx_log_selected {
+            let style = Style::default().fg(Color::Black).bg(theme.primary);
+            (style, style)
+        } else {
+            (Style::default().fg(Color::White), Style::default().fg(theme.dim))
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}", cut(&format_time_ago(entry.timestamp), WHEN_W)), dim_style),
+            Span::styled(format!("{:>w$}  ", app.fmt_bnt(entry.amount), w = amount_w - 2), text_style),
+            Span::styled(cut(&entry.txid, TXID_W), text_style),
+            Span::styled(cut(&entry.address, addr_w), dim_style),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
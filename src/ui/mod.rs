@@ -1,21 +1,154 @@
 pub mod chain;
 pub mod dashboard;
+pub mod history;
+pub mod peers;
 
 use ratatui::{
     Frame,
-    layout::{Constraint, Layout, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Paragraph, Block, Borders, Clear},
+    widgets::{Paragraph, Block, Borders, Clear, Wrap},
 };
 
 use crate::app::App;
+use crate::theme::Theme;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-pub const GREEN: Color = Color::Rgb(170, 255, 0);
-pub const DIM: Color = Color::Rgb(140, 140, 140);
+// below this the views overlap or squash to nothing, so render() shows a
+// notice instead; wide enough for the 52-column popups and the stacked
+// dashboard
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
+
+// split view needs room for both the 2x2 dashboard and a usable grid
+pub const SPLIT_MIN_WIDTH: u16 = 160;
 pub const PLASMA_CHARS: [char; 10] = [' ', '·', '∙', ':', '░', '▒', '▓', '█', '▓', '░'];
 
+// fee rate scale bounds in atomic units per byte (log-scaled between them)
+const FEE_SCALE_LO: f64 = 1.0;
+const FEE_SCALE_HI: f64 = 10_000.0;
+
+/// Map a fee rate (atomic units per byte) onto the shared low→high color
+/// scale: cyan (cheap) → green → red (expensive).
+pub fn fee_color(rate: f64) -> Color {
+    let lo = FEE_SCALE_LO.log10();
+    let hi = FEE_SCALE_HI.log10();
+    let t = ((rate.max(FEE_SCALE_LO).log10() - lo) / (hi - lo)).clamp(0.0, 1.0) as f32;
+    fee_gradient(t)
+}
+
+/// `fee_color` for `fee` paid by a `size`-byte transaction, or `unknown`
+/// when the daemon didn't report the size.
+pub fn tx_fee_color(fee: u64, size: Option<u64>, unknown: Color) -> Color {
+    match size {
+        Some(size) if size > 0 => fee_color(fee as f64 / size as f64),
+        _ => unknown,
+    }
+}
+
+fn fee_gradient(t: f32) -> Color {
+    let lerp = |a: u8, b: u8, t: f32| (a as f32 + (b as f32 - a as f32) * t) as u8;
+    if t < 0.5 {
+        let t = t * 2.0;
+        Color::Rgb(lerp(0, 170, t), lerp(200, 255, t), lerp(255, 0, t))
+    } else {
+        let t = (t - 0.5) * 2.0;
+        Color::Rgb(lerp(170, 255, t), lerp(255, 80, t), lerp(0, 80, t))
+    }
+}
+
+/// One-line legend for `fee_color`: "fee low ▇▇▇▇ high".
+pub fn fee_legend(width: u16, theme: Theme) -> Line<'static> {
+    let label_w = "  fee low  high".len();
+    let bar_w = (width as usize).saturating_sub(label_w).min(24);
+    let mut spans = vec![Span::styled("  fee low ", Style::default().fg(theme.dim))];
+    for i in 0..bar_w {
+        let t = if bar_w > 1 { i as f32 / (bar_w - 1) as f32 } else { 0.0 };
+        spans.push(Span::styled("▇", Style::default().fg(fee_gradient(t))));
+    }
+    spans.push(Span::styled(" high", Style::default().fg(theme.dim)));
+    Line::from(spans)
+}
+
+/// Right end of the help bar: a dot for the status poll (red once a poll
+/// has been missed, yellow while the node syncs, else green) and the local
+/// time.
+fn status_clock(app: &App) -> Line<'static> {
+    let theme = app.theme;
+    let clock = Span::styled(
+        chrono::Local::now().format(" %H:%M ").to_string(),
+        Style::default().fg(theme.dim),
+    );
+    // nothing is polled in a replay
+    if app.replay {
+        return Line::from(clock);
+    }
+    let fresh = app
+        .staleness(app.last_status_tick, app.status_poll_ticks)
+        .is_some_and(|(_, stale)| !stale);
+    let syncing = app.status.as_ref().is_some_and(|s| s.syncing);
+    let (color, word) = match (fresh, syncing) {
+        (false, _) => (Color::Red, "offline"),
+        (true, true) => (theme.warning, "syncing"),
+        (true, false) => (theme.primary, "online"),
+    };
+    let dot = if app.accessible { word } else { "●" };
+    Line::from(vec![Span::styled(format!(" {}", dot), Style::default().fg(color)), clock])
+}
+
+/// Centered stand-in for the whole screen below `MIN_WIDTH`x`MIN_HEIGHT`.
+fn render_too_small(frame: &mut Frame, theme: Theme) {
+    let area = frame.area();
+    if area.is_empty() {
+        return;
+    }
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("Terminal too small (need at least {}x{})", MIN_WIDTH, MIN_HEIGHT),
+            Style::default().fg(theme.warning),
+        )),
+        Line::from(Span::styled(
+            format!("currently {}x{}", area.width, area.height),
+            Style::default().fg(theme.dim),
+        )),
+    ];
+    // rows once wrapped, to center the block vertically
+    let rows: u16 = lines.iter().map(|l| (l.width() as u16).div_ceil(area.width)).sum();
+    let y = area.height.saturating_sub(rows) / 2;
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center).wrap(Wrap { trim: true }),
+        Rect { y: area.y + y, height: area.height - y, ..area },
+    );
+}
+
+/// The end of `text` that fits in `width` columns, with a leading `…` when
+/// cut, so the cursor end of a long input stays visible. Measured in
+/// display width, since addresses and pasted text may hold wide characters.
+fn tail_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut tail = Vec::new();
+    let mut used = 1; // the ellipsis
+    for c in text.chars().rev() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width {
+            break;
+        }
+        used += w;
+        tail.push(c);
+    }
+    std::iter::once('…').chain(tail.into_iter().rev()).collect()
+}
+
 pub fn render(frame: &mut Frame, app: &mut App) {
+    let theme = app.theme;
+    app.screen_size = (frame.area().width, frame.area().height);
+    if frame.area().width < MIN_WIDTH || frame.area().height < MIN_HEIGHT {
+        render_too_small(frame, theme);
+        return;
+    }
     let outer = Layout::vertical([
         Constraint::Length(3),
         Constraint::Min(1),
@@ -23,62 +156,147 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     ])
     .split(frame.area());
 
-    // help bar (always visible)
-    let mut help_spans = vec![
-        Span::styled(" [1]", Style::default().fg(GREEN)),
-        Span::styled(" Dashboard  ", Style::default().fg(DIM)),
-        Span::styled("[2]", Style::default().fg(GREEN)),
-        Span::styled(" Grid  ", Style::default().fg(DIM)),
+    // the peers and history views replace the split while they're open
+    app.split_active =
+        app.split_view && matches!(app.current_view, 1 | 2) && frame.area().width >= SPLIT_MIN_WIDTH;
 
-    ];
+    // help bar (always visible)
+    let mut help_spans = vec![];
+    if app.replay {
+        help_spans.push(Span::styled(" REPLAY ", Style::default().fg(Color::Black).bg(theme.warning)));
+    }
+    if app.readonly {
+        help_spans.push(Span::styled(" READ-ONLY ", Style::default().fg(Color::Black).bg(theme.accent)));
+    }
+    if app.animations_paused {
+        help_spans.push(Span::styled(" PAUSED ", Style::default().fg(Color::Black).bg(theme.dim)));
+    }
+    help_spans.extend([
+        Span::styled(" [1]", Style::default().fg(theme.primary)),
+        Span::styled(" Dashboard  ", Style::default().fg(theme.dim)),
+        Span::styled("[2]", Style::default().fg(theme.primary)),
+        Span::styled(" Grid  ", Style::default().fg(theme.dim)),
+        Span::styled("[3]", Style::default().fg(theme.primary)),
+        Span::styled(" Peers  ", Style::default().fg(theme.dim)),
+        Span::styled("[4]", Style::default().fg(theme.primary)),
+        Span::styled(" History  ", Style::default().fg(theme.dim)),
+        Span::styled("[|]", Style::default().fg(theme.primary)),
+        Span::styled(" Split  ", Style::default().fg(theme.dim)),
+    ]);
 
-    match app.current_view {
-        1 => {
-            help_spans.extend([
-                Span::styled("[s/r]", Style::default().fg(GREEN)),
-                Span::styled(" Send / Receive  ", Style::default().fg(DIM)),
-                Span::styled("[m]", Style::default().fg(GREEN)),
-                Span::styled(" Mine  ", Style::default().fg(DIM)),
-                Span::styled("[+/-]", Style::default().fg(GREEN)),
-                Span::styled(" Threads  ", Style::default().fg(DIM)),
-            ]);
-        }
-        2 => {
-            help_spans.extend([
-                Span::styled("[j/k]", Style::default().fg(GREEN)),
-                Span::styled(" Nav  ", Style::default().fg(DIM)),
-                Span::styled("[J/K]", Style::default().fg(GREEN)),
-                Span::styled(" Jump  ", Style::default().fg(DIM)),
-                Span::styled("[v]", Style::default().fg(GREEN)),
-                Span::styled(" View in Browser  ", Style::default().fg(DIM)),
-            ]);
-        }
-        _ => {}
+    // the split shows both views, and both sets of keys work in it
+    let dashboard_keys = app.split_active || app.current_view == 1;
+    let grid_keys = app.split_active || app.current_view == 2;
+    if dashboard_keys && app.readonly {
+        help_spans.extend([
+            Span::styled("[r]", Style::default().fg(theme.primary)),
+            Span::styled(" Receive  ", Style::default().fg(theme.dim)),
+        ]);
+    } else if dashboard_keys {
+        help_spans.extend([
+            Span::styled("[s/r]", Style::default().fg(theme.primary)),
+            Span::styled(" Send / Receive  ", Style::default().fg(theme.dim)),
+            Span::styled("[m]", Style::default().fg(theme.primary)),
+            Span::styled(" Mine  ", Style::default().fg(theme.dim)),
+            Span::styled("[+/-]", Style::default().fg(theme.primary)),
+            Span::styled(" Threads  ", Style::default().fg(theme.dim)),
+        ]);
+    }
+    if grid_keys {
+        help_spans.extend([
+            Span::styled("[j/k]", Style::default().fg(theme.primary)),
+            Span::styled(" Nav  ", Style::default().fg(theme.dim)),
+            Span::styled("[J/K]", Style::default().fg(theme.primary)),
+            Span::styled(" Jump  ", Style::default().fg(theme.dim)),
+            Span::styled("[v]", Style::default().fg(theme.primary)),
+            Span::styled(" View in Browser  ", Style::default().fg(theme.dim)),
+            Span::styled("[u]", Style::default().fg(theme.primary)),
+            Span::styled(" Refresh  ", Style::default().fg(theme.dim)),
+            Span::styled("[d]", Style::default().fg(theme.primary)),
+            Span::styled(" Detail  ", Style::default().fg(theme.dim)),
+            Span::styled("[t/b]", Style::default().fg(theme.primary)),
+            Span::styled(" Ages/Bars  ", Style::default().fg(theme.dim)),
+            Span::styled("[f]", Style::default().fg(theme.primary)),
+            Span::styled(
+                if app.follow_tip { " Follow: on  " } else { " Follow: off  " },
+                Style::default().fg(theme.dim),
+            ),
+        ]);
     }
 
     help_spans.extend([
-        Span::styled("[q]", Style::default().fg(GREEN)),
-        Span::styled(" Quit", Style::default().fg(DIM)),
+        Span::styled("[q]", Style::default().fg(theme.primary)),
+        Span::styled(" Quit", Style::default().fg(theme.dim)),
     ]);
 
-    frame.render_widget(Paragraph::new(Line::from(help_spans)), outer[2]);
+    let right = status_clock(app);
+    let right_w = (right.width() as u16).min(outer[2].width);
+    let [help_area, clock_area] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(right_w)]).areas(outer[2]);
+    frame.render_widget(Paragraph::new(Line::from(help_spans)), help_area);
+    frame.render_widget(Paragraph::new(right), clock_area);
 
-    match app.current_view {
-        1 => dashboard::render(frame, app, outer[0], outer[1]),
-        2 => chain::render(frame, app, outer[0], outer[1]),
-        _ => {}
+    if app.split_active {
+        let halves = [Constraint::Percentage(50), Constraint::Percentage(50)];
+        let title = Layout::horizontal(halves).split(outer[0]);
+        let content = Layout::horizontal(halves).split(outer[1]);
+        dashboard::render(frame, app, title[0], content[0]);
+        chain::render(frame, app, title[1], content[1]);
+    } else {
+        match app.current_view {
+            1 => dashboard::render(frame, app, outer[0], outer[1]),
+            2 => chain::render(frame, app, outer[0], outer[1]),
+            3 => peers::render(frame, app, outer[0], outer[1]),
+            4 => history::render(frame, app, outer[0], outer[1]),
+            _ => {}
+        }
+    }
+
+    if let Some(ref banner) = app.reorg_banner {
+        let area = frame.area();
+        let line = Rect::new(area.x, area.y, area.width, area.height.min(1));
+        frame.render_widget(Clear, line);
+        frame.render_widget(
+            Paragraph::new(format!(" ⚠ {} · Esc to dismiss", banner)).style(
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            line,
+        );
+    }
+
+    if app.reconnecting() {
+        let area = frame.area();
+        let line = Rect::new(area.x, area.y, area.width, area.height.min(1));
+        let secs = app.reconnect_at.saturating_sub(app.tick_count) * crate::app::TICK_MS / 1000;
+        frame.render_widget(Clear, line);
+        frame.render_widget(
+            Paragraph::new(format!(" Reconnecting… next try in {}s · F5 to retry now", secs)).style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            line,
+        );
     }
 
     // send dialog overlay
     if let crate::app::InputMode::SendDialog {
         ref address,
         ref amount,
+        ref fee,
         focused,
         ref error,
+        ref confirm,
+        ..
     } = app.input_mode
     {
-        let popup_w = 52u16;
-        let popup_h = 11u16;
+        let has_book = !app.address_book.is_empty();
+        let popup_w = 52u16.min(frame.area().width);
+        let popup_h = if has_book { 17u16 } else { 14 }.min(frame.area().height);
         let x = (frame.area().width.saturating_sub(popup_w)) / 2;
         let y = (frame.area().height.saturating_sub(popup_h)) / 2;
         let area = Rect::new(x, y, popup_w, popup_h);
@@ -88,33 +306,51 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             Block::default()
                 .title(" Send BNT ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(GREEN)),
+                .border_style(Style::default().fg(theme.primary)),
             area,
         );
 
-        let inner = Rect::new(x + 2, y + 1, popup_w - 4, popup_h - 2);
-        let fields = Layout::vertical([
+        let inner = Rect::new(
+            x + 2,
+            y + 1,
+            popup_w.saturating_sub(4),
+            popup_h.saturating_sub(2),
+        );
+        let mut rows = vec![
             Constraint::Length(1), // address label
             Constraint::Length(1), // address input
             Constraint::Length(1), // spacer
             Constraint::Length(1), // amount label
             Constraint::Length(1), // amount input
             Constraint::Length(1), // spacer
-            Constraint::Min(1),   // error or instructions
-        ])
-        .split(inner);
+            Constraint::Length(1), // fee label
+            Constraint::Length(1), // fee input
+            Constraint::Length(1), // spacer
+        ];
+        if has_book {
+            rows.extend([
+                Constraint::Length(1), // address book label
+                Constraint::Length(1), // address book picker
+                Constraint::Length(1), // spacer
+            ]);
+        }
+        rows.push(Constraint::Min(1)); // error or instructions
+        let fields = Layout::vertical(rows).split(inner);
+        let status_area = fields[fields.len() - 1];
 
-        let addr_color = if focused == 0 { GREEN } else { DIM };
-        let amt_color = if focused == 1 { GREEN } else { DIM };
+        let addr_color = if focused == 0 { theme.primary } else { theme.dim };
+        let amt_color = if focused == 1 { theme.primary } else { theme.dim };
+        let fee_label_color = if focused == 2 { theme.primary } else { theme.dim };
 
-        frame.render_widget(
-            Paragraph::new(Span::styled("Address:", Style::default().fg(addr_color))),
-            fields[0],
-        );
-        let addr_cursor = if focused == 0 { "_" } else { "" };
+        let mut addr_label = vec![Span::styled("Address:", Style::default().fg(addr_color))];
+        if let Some(label) = app.address_book.label_for(address) {
+            addr_label.push(Span::styled(format!(" {}", label), Style::default().fg(Color::White)));
+        }
+        frame.render_widget(Paragraph::new(Line::from(addr_label)), fields[0]);
+        let addr_cursor = if focused == 0 && confirm.is_none() { "_" } else { "" };
         frame.render_widget(
             Paragraph::new(Span::styled(
-                format!("{}{}", address, addr_cursor),
+                tail_to_width(&format!("{}{}", address, addr_cursor), fields[1].width as usize),
                 Style::default().fg(Color::White),
             )),
             fields[1],
@@ -124,34 +360,460 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             Paragraph::new(Span::styled("Amount (BNT):", Style::default().fg(amt_color))),
             fields[3],
         );
-        let amt_cursor = if focused == 1 { "_" } else { "" };
+        let amt_cursor = if focused == 1 && confirm.is_none() { "_" } else { "" };
         frame.render_widget(
             Paragraph::new(Span::styled(
-                format!("{}{}", amount, amt_cursor),
+                tail_to_width(&format!("{}{}", amount, amt_cursor), fields[4].width as usize),
                 Style::default().fg(Color::White),
             )),
             fields[4],
         );
 
-        if let Some(err) = error {
+        frame.render_widget(
+            Paragraph::new(Span::styled("Fee (BNT, blank = auto):", Style::default().fg(fee_label_color))),
+            fields[6],
+        );
+        let fee_cursor = if focused == 2 && confirm.is_none() { "_" } else { "" };
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                tail_to_width(&format!("{}{}", fee, fee_cursor), fields[7].width as usize),
+                Style::default().fg(Color::White),
+            )),
+            fields[7],
+        );
+        // the daemon's suggestion, in the spacer under the fee
+        if let Some(ref estimate) = app.fee_estimate {
+            let hint = if fee.is_empty() {
+                format!("auto will likely pay {}", app.fmt_bnt(estimate.normal))
+            } else {
+                format!("suggested fee: {}", app.fmt_bnt(estimate.normal))
+            };
+            frame.render_widget(Paragraph::new(Span::styled(hint, Style::default().fg(theme.dim))), fields[8]);
+        }
+
+        if has_book {
+            let book_color = if focused == 3 { theme.primary } else { theme.dim };
+            frame.render_widget(
+                Paragraph::new(Span::styled("Address book (←/→ pick):", Style::default().fg(book_color))),
+                fields[9],
+            );
+            let picked = match app.address_book.label_for(address) {
+                Some(label) => format!("◀ {} ▶", label),
+                None => format!("◀ {} saved ▶", app.address_book.entries.len()),
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    tail_to_width(&picked, fields[10].width as usize),
+                    Style::default().fg(Color::White),
+                )),
+                fields[10],
+            );
+        }
+
+        if let Some(typed) = confirm {
+            let limit = app.config.wallet.max_send_atomic().unwrap_or(0);
+            let mut lines = vec![
+                Line::from(Span::styled(
+                    format!("Over the {} limit. Type CONFIRM:", app.fmt_bnt(limit)),
+                    Style::default().fg(theme.warning),
+                )),
+                Line::from(Span::styled(
+                    tail_to_width(&format!("{}_", typed), status_area.width as usize),
+                    Style::default().fg(Color::White),
+                )),
+            ];
+            lines.push(match error {
+                Some(err) => Line::from(Span::styled(err.as_str(), Style::default().fg(Color::Red))),
+                None => Line::from(Span::styled(
+                    "Enter send · Esc back",
+                    Style::default().fg(theme.dim),
+                )),
+            });
+            frame.render_widget(Paragraph::new(lines), status_area);
+        } else if let Some(err) = error {
             frame.render_widget(
                 Paragraph::new(Span::styled(
                     err.as_str(),
                     Style::default().fg(Color::Red),
                 )),
-                fields[6],
+                status_area,
+            );
+        } else if let Some(warning) = app.send_amount_warning(amount) {
+            frame.render_widget(
+                Paragraph::new(Span::styled(warning, Style::default().fg(theme.warning))),
+                status_area,
             );
         } else {
             frame.render_widget(
                 Paragraph::new(Span::styled(
-                    "Tab switch · Enter send · Esc cancel",
-                    Style::default().fg(DIM),
+                    "Tab switch · ^V paste · Enter send · Esc cancel",
+                    Style::default().fg(theme.dim),
                 )),
-                fields[6],
+                status_area,
             );
         }
     }
 
+    // wallet creation prompt
+    if let crate::app::InputMode::CreateWallet { ref error } = app.input_mode {
+        let popup_w = 48u16.min(frame.area().width);
+        let popup_h = 6u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                " This node has no wallet yet.",
+                Style::default().fg(Color::White),
+            )),
+            Line::from(Span::styled(
+                " Create one now?",
+                Style::default().fg(Color::White),
+            )),
+        ];
+        match error {
+            Some(err) => lines.push(Line::from(Span::styled(
+                format!(" {}", err),
+                Style::default().fg(Color::Red),
+            ))),
+            None => lines.push(Line::from("")),
+        }
+        lines.push(Line::from(Span::styled(
+            " y create · n/Esc cancel",
+            Style::default().fg(theme.dim),
+        )));
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Create Wallet ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // wallet lock prompt
+    if let crate::app::InputMode::LockWallet { ref error } = app.input_mode {
+        let popup_w = 48u16.min(frame.area().width);
+        let popup_h = 6u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let mut lines = vec![
+            Line::from(Span::styled(" Lock the wallet now?", Style::default().fg(Color::White))),
+            Line::from(Span::styled(
+                " Sends will ask for the passphrase again.",
+                Style::default().fg(theme.dim),
+            )),
+        ];
+        match error {
+            Some(err) => lines.push(Line::from(Span::styled(
+                format!(" {}", err),
+                Style::default().fg(Color::Red),
+            ))),
+            None => lines.push(Line::from("")),
+        }
+        lines.push(Line::from(Span::styled(" y lock · n/Esc cancel", Style::default().fg(theme.dim))));
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Lock Wallet ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // receive address, with the ones auto-rotation moved away from below
+    if let crate::app::InputMode::Receive { selected } = app.input_mode {
+        let previous = app.previous_addresses.len() as u16;
+        let popup_w = 96u16.min(frame.area().width);
+        let popup_h = (if previous > 0 { 6 + previous } else { 5 }).min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let addr_w = popup_w.saturating_sub(4) as usize;
+        let row = |i: usize| {
+            let addr = tail_to_width(app.receive_address(i).unwrap_or_default(), addr_w);
+            let style = if i == selected {
+                Style::default().fg(Color::Black).bg(theme.primary)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(Span::styled(format!(" {} ", addr), style))
+        };
+        let mut lines = vec![row(0)];
+        if previous > 0 {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(" Previous", Style::default().fg(theme.dim))));
+            lines.extend((1..=previous as usize).map(row));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " j/k select · Enter copy · Esc close",
+            Style::default().fg(theme.dim),
+        )));
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Receive ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // find-transaction prompt
+    if let crate::app::InputMode::FindTx {
+        ref query,
+        ref error,
+    } = app.input_mode
+    {
+        let popup_w = 72u16.min(frame.area().width);
+        let popup_h = 5u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let status = match error {
+            Some(err) => Span::styled(format!(" {}", err), Style::default().fg(Color::Red)),
+            None => Span::styled(" Enter find · Esc cancel", Style::default().fg(theme.dim)),
+        };
+        let lines = vec![
+            Line::from(Span::styled(format!(" {}_", query), Style::default().fg(Color::White))),
+            Line::from(""),
+            Line::from(status),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Find Transaction ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // label prompt for the address book
+    if let crate::app::InputMode::SaveAddress {
+        ref address,
+        ref label,
+        ref error,
+    } = app.input_mode
+    {
+        let popup_w = 52u16.min(frame.area().width);
+        let popup_h = 6u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let inner_w = popup_w.saturating_sub(2) as usize;
+        let status = match error {
+            Some(err) => Span::styled(format!(" {}", err), Style::default().fg(Color::Red)),
+            None => Span::styled(" Enter save · Esc cancel", Style::default().fg(theme.dim)),
+        };
+        let lines = vec![
+            Line::from(Span::styled(
+                tail_to_width(&format!(" {}", address), inner_w),
+                Style::default().fg(theme.dim),
+            )),
+            Line::from(Span::styled(
+                tail_to_width(&format!(" Label: {}_", label), inner_w),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(""),
+            Line::from(status),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Save Address ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // wallet passphrase prompt; only the length is drawn
+    if let crate::app::InputMode::UnlockWallet {
+        ref passphrase,
+        ref error,
+        ref send,
+    } = app.input_mode
+    {
+        let popup_w = 52u16.min(frame.area().width);
+        let popup_h = 6u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let purpose = match send {
+            Some(send) => format!(" The wallet is locked. Unlock to send {}.", app.fmt_bnt(send.amount)),
+            None => format!(" Unlock the wallet for {} minutes.", crate::app::UNLOCK_SECS / 60),
+        };
+        let status = match error {
+            Some(err) => Span::styled(format!(" {}", err), Style::default().fg(Color::Red)),
+            None => Span::styled(" Enter unlock · Esc cancel", Style::default().fg(theme.dim)),
+        };
+        let lines = vec![
+            Line::from(Span::styled(purpose, Style::default().fg(theme.dim))),
+            Line::from(Span::styled(
+                format!(" {}_", "•".repeat(passphrase.chars().count())),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(""),
+            Line::from(status),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Unlock Wallet ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // mempool txid filter; the list behind it narrows as you type
+    if let crate::app::InputMode::MempoolFilter { .. } = app.input_mode {
+        let popup_w = 44u16.min(frame.area().width);
+        let popup_h = 5u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let matches = app.filtered_mempool_txs().len();
+        let lines = vec![
+            Line::from(Span::styled(
+                format!(" {}_", app.mempool_txs_filter),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(" {} match · Enter keep · Esc cancel", matches),
+                Style::default().fg(theme.dim),
+            )),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Filter by Txid Prefix ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // go-to-block prompt
+    if let crate::app::InputMode::GotoBlock {
+        ref input,
+        ref error,
+    } = app.input_mode
+    {
+        let popup_w = 40u16.min(frame.area().width);
+        let popup_h = 5u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let status = match error {
+            Some(err) => Span::styled(format!(" {}", err), Style::default().fg(Color::Red)),
+            None => Span::styled(" Enter go · Esc cancel", Style::default().fg(theme.dim)),
+        };
+        let lines = vec![
+            Line::from(Span::styled(format!(" #{}_", input), Style::default().fg(Color::White))),
+            Line::from(""),
+            Line::from(status),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Go to Block ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    // raw transaction broadcast dialog
+    if let crate::app::InputMode::Broadcast {
+        ref hex,
+        confirm,
+        ref error,
+    } = app.input_mode
+    {
+        let popup_w = 64u16.min(frame.area().width);
+        let popup_h = 7u16.min(frame.area().height);
+        let x = (frame.area().width.saturating_sub(popup_w)) / 2;
+        let y = (frame.area().height.saturating_sub(popup_h)) / 2;
+        let area = Rect::new(x, y, popup_w, popup_h);
+
+        frame.render_widget(Clear, area);
+        let shown = tail_to_width(hex, popup_w.saturating_sub(5) as usize);
+        let status = match error {
+            Some(err) => Line::from(Span::styled(
+                format!(" {}", err),
+                Style::default().fg(Color::Red),
+            )),
+            None if confirm => Line::from(Span::styled(
+                format!(" Broadcast {} bytes? Enter again to confirm", hex.len() / 2),
+                Style::default().fg(theme.warning),
+            )),
+            None => Line::from(Span::styled(
+                " Paste signed tx hex · Enter check · Esc cancel",
+                Style::default().fg(theme.dim),
+            )),
+        };
+        let lines = vec![
+            Line::from(Span::styled(" Raw transaction (hex):", Style::default().fg(theme.primary))),
+            Line::from(Span::styled(
+                format!(" {}_", shown),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(""),
+            status,
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" Broadcast Transaction ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.primary)),
+            ),
+            area,
+        );
+    }
+
+    if let crate::app::InputMode::TxDetail { .. } = app.input_mode {
+        chain::render_tx_detail(frame, app, outer[0].union(outer[1]));
+    }
+
+    if app.show_events {
+        render_event_log(frame, app);
+    }
+    if app.show_peer_summary {
+        render_peer_summary(frame, app);
+    }
+
     // flash message overlay
     if let Some(ref flash) = app.flash_message {
         let hint = if flash.copyable.is_some() {
@@ -161,11 +823,11 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         } else {
             ""
         };
-        let content_w = flash.text.len().max(hint.len()) as u16 + 4;
+        let content_w = flash.text.width().max(hint.width()) as u16 + 4;
         let h = if hint.is_empty() { 3u16 } else { 4u16 };
         let x = (frame.area().width.saturating_sub(content_w)) / 2;
         let y = frame.area().height / 2;
-        let area = Rect::new(x, y, content_w, h);
+        let area = Rect::new(x, y, content_w, h).intersection(frame.area());
         frame.render_widget(Clear, area);
 
         let mut lines = vec![
@@ -177,7 +839,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         if !hint.is_empty() {
             lines.push(Line::from(Span::styled(
                 format!(" {} ", hint),
-                Style::default().fg(DIM),
+                Style::default().fg(theme.dim),
             )));
         }
         frame.render_widget(
@@ -185,9 +847,465 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(GREEN)),
+                        .border_style(Style::default().fg(theme.primary)),
                 ),
             area,
         );
     }
+
+    if app.accessible {
+        asciify(frame.buffer_mut());
+    }
+}
+
+/// Replace box-drawing, block, braille and other decorative glyphs with
+/// plain ASCII, for `--accessible`.
+fn asciify(buf: &mut ratatui::buffer::Buffer) {
+    for cell in buf.content.iter_mut() {
+        let mut chars = cell.symbol().chars();
+        if let (Some(c), None) = (chars.next(), chars.next())
+            && let Some(ascii) = ascii_fallback(c)
+        {
+            cell.set_char(ascii);
+        }
+    }
+}
+
+fn ascii_fallback(c: char) -> Option<char> {
+    Some(match c {
+        c if c.is_ascii() => return None,
+        '─' | '━' | '═' | '╌' | '┄' | '┈' | '╴' | '╶' => '-',
+        '│' | '┃' | '║' | '╎' | '┆' | '┊' | '╵' | '╷' | '▏' => '|',
+        '\u{2500}'..='\u{257F}' => '+',
+        '░' => '.',
+        '▒' => ':',
+        '\u{2580}'..='\u{259F}' | '▰' => '#',
+        '▱' => '-',
+        '\u{2800}' => ' ',
+        '\u{2801}'..='\u{28FF}' => '.',
+        '·' | '∙' | '•' | '…' => '.',
+        '●' => '*',
+        '○' => 'o',
+        '≈' => '~',
+        '—' | '–' => '-',
+        '⚠' => '!',
+        _ => return None,
+    })
+}
+
+/// Event log overlay: the most recent events that fit, newest at the bottom.
+fn render_event_log(frame: &mut Frame, app: &App) {
+    let theme = app.theme;
+    let full = frame.area();
+    let w = (full.width * 4 / 5).max(full.width.min(40));
+    let h = (full.height * 3 / 4).max(full.height.min(8));
+    let area = Rect::new(
+        full.x + (full.width - w) / 2,
+        full.y + (full.height - h) / 2,
+        w,
+        h,
+    );
+    frame.render_widget(Clear, area);
+
+    let rows = h.saturating_sub(2) as usize;
+    let skip = app.events.len().saturating_sub(rows);
+    let lines: Vec<Line> = if app.events.is_empty() {
+        vec![Line::from(Span::styled(" No events yet", Style::default().fg(theme.dim)))]
+    } else {
+        app.events
+            .iter()
+            .skip(skip)
+            .map(|e| {
+                let secs = e.time % 86_400;
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:02}:{:02}:{:02} ", secs / 3600, secs % 3600 / 60, secs % 60),
+                        Style::default().fg(theme.dim),
+                    ),
+                    Span::styled(e.msg.clone(), Style::default().fg(Color::White)),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title(" Events (UTC) ")
+                .title_bottom(Line::from(" L/Esc close ").right_aligned())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary)),
+        ),
+        area,
+    );
+}
+
+/// Peer summary overlay: inbound/outbound counts and the busiest subnets as
+/// a histogram. Pure aggregation over the peer list; no lookups.
+fn render_peer_summary(frame: &mut Frame, app: &App) {
+    let theme = app.theme;
+    let full = frame.area();
+    let w = full.width.min(56);
+    let h = (full.height * 3 / 4).max(full.height.min(10));
+    let area = Rect::new(
+        full.x + (full.width - w) / 2,
+        full.y + (full.height - h) / 2,
+        w,
+        h,
+    );
+    frame.render_widget(Clear, area);
+
+    // this node's identity, middle-truncated to fit
+    let id_w = (w as usize).saturating_sub(2 + 7);
+    let node_id = match app.status.as_ref().map(|s| s.peer_id.as_str()) {
+        Some(id) if !id.is_empty() => {
+            let chars: Vec<char> = id.chars().collect();
+            if chars.len() <= id_w {
+                id.to_string()
+            } else {
+                let half = id_w.saturating_sub(1) / 2;
+                let head: String = chars[..half].iter().collect();
+                let tail: String = chars[chars.len() - half..].iter().collect();
+                format!("{}…{}", head, tail)
+            }
+        }
+        _ => "—".to_string(),
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" Node  ", Style::default().fg(theme.dim)),
+            Span::styled(node_id, Style::default().fg(Color::White)),
+        ]),
+        Line::from(""),
+    ];
+    match (&app.peers, &app.peers_error) {
+        (_, Some(err)) => {
+            lines.push(Line::from(Span::styled(format!(" {}", err), Style::default().fg(Color::Red))));
+        }
+        (None, None) => {
+            lines.push(Line::from(Span::styled(" Loading…", Style::default().fg(theme.dim))));
+        }
+        (Some(peers), None) if peers.is_empty() => {
+            lines.push(Line::from(Span::styled(" No peers connected", Style::default().fg(theme.dim))));
+        }
+        (Some(peers), None) => {
+            let summary = crate::types::summarize_peers(peers);
+            // label column + count column + borders
+            let bar_w = (w as usize).saturating_sub(2 + 20 + 5);
+            let max = summary
+                .inbound
+                .max(summary.outbound)
+                .max(summary.subnets.first().map_or(0, |s| s.1))
+                .max(1);
+            let row = |label: &str, count: usize, color: Color| {
+                let len = (count * bar_w).div_ceil(max);
+                Line::from(vec![
+                    Span::styled(format!(" {:<19}", label), Style::default().fg(theme.dim)),
+                    Span::styled("█".repeat(len), Style::default().fg(color)),
+                    Span::styled(format!(" {}", count), Style::default().fg(Color::White)),
+                ])
+            };
+
+            lines.push(row("Outbound", summary.outbound, theme.primary));
+            lines.push(row("Inbound", summary.inbound, Color::Cyan));
+            if summary.other > 0 {
+                lines.push(row("Unknown direction", summary.other, theme.dim));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(" {} subnets", summary.subnets.len()),
+                Style::default().fg(Color::White),
+            )));
+            let room = (h as usize).saturating_sub(2 + lines.len() + 1);
+            for (subnet, count) in summary.subnets.iter().take(room) {
+                lines.push(row(subnet, *count, theme.warning));
+            }
+            if summary.unparsed > 0 {
+                lines.push(row("Not an IP", summary.unparsed, theme.dim));
+            }
+        }
+    }
+
+    let total = app.peers.as_ref().map_or(0, |p| p.len());
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(" Peers ({}) ", total))
+                .title_bottom(Line::from(" [i] copy node ID · n/Esc close ").right_aligned())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary)),
+        ),
+        area,
+    );
+}
+
+/// Render every view (and the dialog/flash overlays) at every terminal size
+/// up to `max_w`×`max_h` into an off-screen buffer, returning the first size
+/// that panics. Used by `--replay <file> --resize-stress` to shake out layout
+/// bugs.
+pub fn stress_resize(app: &mut App, max_w: u16, max_h: u16) -> Result<usize, String> {
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut rendered = 0;
+    let mut failure = None;
+    if app.mempool_txs.is_none() {
+        let tx = crate::types::MempoolTx { txid: "ab".repeat(32), fee: 12_345, size: 250 };
+        app.mempool_txs = Some(vec![tx; 40]);
+    }
+    if app.fee_estimate.is_none() {
+        app.fee_estimate = Some(crate::types::FeeEstimate { low: 1_000, normal: 12_345, high: 987_654_321 });
+    }
+    if app.address_book.is_empty() {
+        app.address_book.entries = vec![("長い名前のラベル".repeat(4), "x".repeat(80))];
+    }
+    'sizes: for w in 0..=max_w {
+        for h in 0..=max_h {
+            for (view, split, overlay) in [
+                (1, false, false),
+                (2, false, false),
+                (3, false, false),
+                (4, false, false),
+                (1, true, false),
+                (1, false, true),
+            ] {
+                app.current_view = view;
+                app.split_view = split;
+                app.left_panel_mode = match (w + h) % 3 {
+                    0 => crate::app::LeftPanelMode::Balanced,
+                    1 => crate::app::LeftPanelMode::Cube,
+                    _ => crate::app::LeftPanelMode::Info,
+                };
+                if overlay {
+                    app.input_mode = crate::app::InputMode::SendDialog {
+                        address: "x".repeat(80),
+                        amount: "1".into(),
+                        fee: "0.001".into(),
+                        focused: 0,
+                        // wide and multibyte text must not break the popups
+                        error: Some("残高不足 — недостаточно средств 💸".into()),
+                        confirm: (w % 2 == 0).then(|| "CONF".into()),
+                        saved: None,
+                    };
+                    app.set_flash_persistent("送金失敗 ✗ ".repeat(10), String::new());
+                    app.show_events = true;
+                    app.show_peer_summary = true;
+                    app.show_mempool_txs = true;
+                } else {
+                    app.input_mode = crate::app::InputMode::Normal;
+                    app.flash_message = None;
+                    app.show_events = false;
+                    app.show_peer_summary = false;
+                    app.show_mempool_txs = false;
+                }
+                let result = catch_unwind(AssertUnwindSafe(|| {
+                    let mut terminal = Terminal::new(TestBackend::new(w, h)).unwrap();
+                    terminal.draw(|frame| render(frame, app)).map(|_| ())
+                }));
+                match result {
+                    Ok(Ok(())) => rendered += 1,
+                    Ok(Err(e)) => {
+                        failure = Some(format!("{}x{} view {}: {}", w, h, view, e));
+                        break 'sizes;
+                    }
+                    Err(payload) => {
+                        let msg = payload
+                            .downcast_ref::<String>()
+                            .cloned()
+                            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                            .unwrap_or_default();
+                        failure = Some(format!(
+                            "{}x{} view {} (split {}) panicked: {}",
+                            w, h, view, split, msg
+                        ));
+                        break 'sizes;
+                    }
+                }
+            }
+        }
+    }
+
+    std::panic::set_hook(default_hook);
+    app.input_mode = crate::app::InputMode::Normal;
+    app.flash_message = None;
+    app.show_events = false;
+    app.show_peer_summary = false;
+    match failure {
+        Some(f) => Err(f),
+        None => Ok(rendered),
+    }
+}
+
+/// Render the current screen off-screen and return it as plain text, one
+/// line per row with colors stripped and trailing blanks trimmed.
+pub fn screen_text(app: &mut App, width: u16, height: u16) -> String {
+    render_offscreen(app, width, height).map_or_else(String::new, |buf| buffer_text(&buf))
+}
+
+/// Like `screen_text`, but keeping colors and modifiers as ANSI escapes, for
+/// feeding terminal recorders.
+pub fn screen_ansi(app: &mut App, width: u16, height: u16) -> String {
+    render_offscreen(app, width, height).map_or_else(String::new, |buf| buffer_ansi(&buf))
+}
+
+fn render_offscreen(app: &mut App, width: u16, height: u16) -> Option<ratatui::buffer::Buffer> {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).ok()?;
+    terminal.draw(|frame| render(frame, app)).ok()?;
+    Some(terminal.backend().buffer().clone())
+}
+
+fn buffer_ansi(buf: &ratatui::buffer::Buffer) -> String {
+    let area = buf.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut last = None;
+        let mut x = area.left();
+        while x < area.right() {
+            let cell = &buf[(x, y)];
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if last != Some(style) {
+                out.push_str("\x1b[0");
+                if cell.modifier.contains(Modifier::BOLD) {
+                    out.push_str(";1");
+                }
+                if cell.modifier.contains(Modifier::DIM) {
+                    out.push_str(";2");
+                }
+                out.push_str(&ansi_color(cell.fg, 30));
+                out.push_str(&ansi_color(cell.bg, 40));
+                out.push('m');
+                last = Some(style);
+            }
+            let symbol = cell.symbol();
+            out.push_str(symbol);
+            x += symbol.width().max(1) as u16;
+        }
+        out.push_str("\x1b[0m\r\n");
+    }
+    out
+}
+
+/// SGR parameter for `color`; `base` is 30 for foreground, 40 for background.
+fn ansi_color(color: Color, base: u8) -> String {
+    let named = |n: u8| format!(";{}", base + n);
+    let bright = |n: u8| format!(";{}", base + 60 + n);
+    match color {
+        Color::Reset => String::new(),
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => bright(0),
+        Color::LightRed => bright(1),
+        Color::LightGreen => bright(2),
+        Color::LightYellow => bright(3),
+        Color::LightBlue => bright(4),
+        Color::LightMagenta => bright(5),
+        Color::LightCyan => bright(6),
+        Color::White => bright(7),
+        Color::Rgb(r, g, b) => format!(";{};2;{};{};{}", base + 8, r, g, b),
+        Color::Indexed(i) => format!(";{};5;{}", base + 8, i),
+    }
+}
+
+fn buffer_text(buf: &ratatui::buffer::Buffer) -> String {
+    let area = buf.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut x = area.left();
+        while x < area.right() {
+            let symbol = buf[(x, y)].symbol();
+            line.push_str(symbol);
+            // a wide glyph owns the cells after it; skip their filler
+            x += symbol.width().max(1) as u16;
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MIN_HEIGHT, MIN_WIDTH, render, stress_resize};
+    use crate::app::App;
+    use crate::types::{BlockResponse, BlockTransaction};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn app_with_blocks() -> App {
+        let mut app = App::new();
+        for height in 1..=300u64 {
+            let transactions: Vec<_> = (0..height % 5)
+                .map(|i| BlockTransaction {
+                    hash: format!("{:064x}", height * 10 + i),
+                    fee: 1_000 * (i + 1),
+                    inputs: 1,
+                    outputs: 2,
+                    is_coinbase: i == 0,
+                    total_out: Some(5_000_000),
+                    size: Some(2_000),
+                })
+                .collect();
+            app.chain_blocks.push(BlockResponse {
+                height,
+                hash: format!("{:064x}", height),
+                timestamp: 1_700_000_000 + height * 300,
+                difficulty: 100_000 + height,
+                tx_count: transactions.len() as u32,
+                confirmations: 301 - height,
+                reward: 5_000_000_000,
+                transactions,
+            });
+            app.block_cubes.push(crate::cube::SpinCube::new());
+        }
+        app.selected = app.chain_blocks.len() - 1;
+        app
+    }
+
+    fn screen_text(app: &mut App, w: u16, h: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(w, h)).unwrap();
+        terminal.draw(|frame| render(frame, app)).unwrap();
+        terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn every_size_up_to_past_the_minimum_renders() {
+        let mut app = app_with_blocks();
+        // covers the too-small notice and the smallest real layouts
+        let rendered = stress_resize(&mut app, MIN_WIDTH + 20, MIN_HEIGHT + 10).unwrap();
+        assert!(rendered > 0);
+    }
+
+    #[test]
+    fn below_the_minimum_shows_the_notice() {
+        let mut app = app_with_blocks();
+        for (w, h) in [(MIN_WIDTH - 1, MIN_HEIGHT), (MIN_WIDTH, MIN_HEIGHT - 1), (20, 5)] {
+            let text = screen_text(&mut app, w, h);
+            assert!(text.contains("Terminal too small"), "no notice at {}x{}", w, h);
+        }
+        assert!(!screen_text(&mut app, MIN_WIDTH, MIN_HEIGHT).contains("Terminal too small"));
+    }
+
+    #[test]
+    fn receive_dialog_lists_previous_addresses() {
+        let mut app = app_with_blocks();
+        app.rotate_address("current".repeat(12));
+        app.rotate_address("newer".repeat(12));
+        app.input_mode = crate::app::InputMode::Receive { selected: 1 };
+        let text = screen_text(&mut app, 120, 40);
+        assert!(text.contains(&"newer".repeat(12)));
+        assert!(text.contains("Previous"));
+        assert!(text.contains(&"current".repeat(12)));
+        // too narrow for a whole address: cut, not wrapped or panicking
+        assert!(screen_text(&mut app, MIN_WIDTH, MIN_HEIGHT).contains("…"));
+    }
 }
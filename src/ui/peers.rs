@@ -0,0 +1,114 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::app::App;
+
+// column widths; the address column takes whatever is left
+const DIR_W: usize = 5;
+const VERSION_W: usize = 16;
+const PING_W: usize = 9;
+
+pub fn render(frame: &mut Frame, app: &mut App, title_area: Rect, content_area: Rect) {
+    let theme = app.theme;
+    let full = Rect {
+        x: title_area.x,
+        y: title_area.y,
+        width: title_area.width.max(content_area.width),
+        height: title_area.height + content_area.height,
+    };
+
+    let title = match &app.peers {
+        Some(peers) if app.peers_supported => format!(" Peers · {} connected ", peers.len()),
+        _ => " Peers ".to_string(),
+    };
+    let border = Block::default()
+        .title(title)
+        .title_bottom(Line::from(" [j/k] scroll · [n] summary ").right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.primary));
+    let inner = border.inner(full);
+    frame.render_widget(border, full);
+    if inner.height == 0 {
+        return;
+    }
+    // one row goes to the column header
+    let rows = (inner.height as usize).saturating_sub(1);
+    app.peers_rows = rows;
+
+    let message = |text: String, color: Color| {
+        Paragraph::new(Line::from(Span::styled(format!(" {}", text), Style::default().fg(color))))
+    };
+    let peers = match (&app.peers, &app.peers_error) {
+        _ if !app.peers_supported => {
+            frame.render_widget(message("Unsupported: this daemon doesn't list peers".into(), theme.dim), inner);
+            return;
+        }
+        (_, Some(err)) => {
+            frame.render_widget(message(err.clone(), Color::Red), inner);
+            return;
+        }
+        (None, None) => {
+            frame.render_widget(message("Loading…".into(), theme.dim), inner);
+            return;
+        }
+        (Some(peers), None) if peers.is_empty() => {
+            frame.render_widget(message("No peers connected".into(), theme.dim), inner);
+            return;
+        }
+        (Some(peers), None) => peers,
+    };
+
+    let addr_w = (inner.width as usize).saturating_sub(1 + DIR_W + VERSION_W + PING_W).max(8);
+    let cell = |text: &str, w: usize| {
+        let mut s: String = text.chars().take(w.saturating_sub(1)).collect();
+        if text.chars().count() >= w {
+            s.pop();
+            s.push('…');
+        }
+        format!("{:<w$}", s, w = w)
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            " {}{}{}{:>w$}",
+            cell("Address", addr_w),
+            cell("Dir", DIR_W),
+            cell("Version", VERSION_W),
+            "Ping",
+            w = PING_W - 1
+        ),
+        Style::default().fg(theme.dim),
+    ))];
+
+    let start = app.peers_scroll.min(peers.len().saturating_sub(rows));
+    for peer in peers.iter().skip(start).take(rows) {
+        let (dir, dir_color) = if peer.is_inbound() {
+            ("in", Color::Cyan)
+        } else if peer.is_outbound() {
+            ("out", theme.primary)
+        } else {
+            ("?", theme.dim)
+        };
+        let ping = peer.ping.map_or("—".to_string(), |ms| format!("{:.0} ms", ms));
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}", cell(&peer.address, addr_w)), Style::default().fg(Color::White)),
+            Span::styled(cell(dir, DIR_W), Style::default().fg(dir_color)),
+            Span::styled(cell(&peer.version, VERSION_W), Style::default().fg(theme.dim)),
+            Span::styled(format!("{:>w$}", ping, w = PING_W - 1), Style::default().fg(Color::White)),
+        ]));
+    }
+    if start + rows < peers.len()
+        && let Some(last) = lines.last_mut()
+    {
+        *last = Line::from(Span::styled(
+            format!(" +{} more...", peers.len() - start - rows + 1),
+            Style::default().fg(theme.dim),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}